@@ -0,0 +1,206 @@
+use std::sync::Arc;
+
+use analogz::containers::{ArcStr, Buffer};
+use arrow_array::{
+    ArrayRef, RecordBatch, RecordBatchOptions, StringArray, TimestampMicrosecondArray,
+};
+use arrow_schema::{ArrowError, DataType, Field, Schema, TimeUnit};
+
+use crate::{
+    datetime::DateTime,
+    extractor::{Extractor, par_extract_values},
+    ip_addr::IpAddr,
+    r#match::Match,
+    socket_addr::SocketAddr,
+};
+
+/// A value that can be stored in an Arrow array.
+pub trait ArrowValue: Sized {
+    fn data_type() -> DataType;
+
+    /// Builds an array from the values, with nulls for `None`.
+    fn to_array(values: &[Option<Self>]) -> ArrayRef;
+}
+
+/// Date times become timestamps in microseconds without a time zone, as they
+/// are naive.
+impl ArrowValue for DateTime {
+    fn data_type() -> DataType {
+        DataType::Timestamp(TimeUnit::Microsecond, None)
+    }
+
+    fn to_array(values: &[Option<Self>]) -> ArrayRef {
+        Arc::new(TimestampMicrosecondArray::from_iter(
+            values
+                .iter()
+                .map(|v| v.as_ref().map(|dt| dt.and_utc().timestamp_micros())),
+        ))
+    }
+}
+
+impl ArrowValue for IpAddr {
+    fn data_type() -> DataType {
+        DataType::Utf8
+    }
+
+    fn to_array(values: &[Option<Self>]) -> ArrayRef {
+        Arc::new(StringArray::from_iter(
+            values.iter().map(|v| v.as_ref().map(ToString::to_string)),
+        ))
+    }
+}
+
+impl ArrowValue for SocketAddr {
+    fn data_type() -> DataType {
+        DataType::Utf8
+    }
+
+    fn to_array(values: &[Option<Self>]) -> ArrayRef {
+        Arc::new(StringArray::from_iter(
+            values.iter().map(|v| v.as_ref().map(ToString::to_string)),
+        ))
+    }
+}
+
+/// Matched categories, e.g. of a `DictionaryExtractor`, keep only the value.
+impl ArrowValue for Match<ArcStr> {
+    fn data_type() -> DataType {
+        DataType::Utf8
+    }
+
+    fn to_array(values: &[Option<Self>]) -> ArrayRef {
+        Arc::new(StringArray::from_iter(
+            values
+                .iter()
+                .map(|v| v.as_ref().map(|m| m.value().as_str())),
+        ))
+    }
+}
+
+/// An extractor whose values can be collected into an Arrow array. It is
+/// implemented for every [`Extractor`] with an [`ArrowValue`] output, and is
+/// object safe so extractors of different outputs can be mixed.
+pub trait ArrowExtractor: Sync {
+    fn data_type(&self) -> DataType;
+
+    /// Extracts a value from every line of `buffer`, in parallel.
+    fn extract_array(&self, buffer: &Buffer) -> ArrayRef;
+}
+
+impl<E> ArrowExtractor for E
+where
+    E: Extractor + Sync,
+    E::Output: ArrowValue + Send,
+{
+    fn data_type(&self) -> DataType {
+        E::Output::data_type()
+    }
+
+    fn extract_array(&self, buffer: &Buffer) -> ArrayRef {
+        E::Output::to_array(&par_extract_values(buffer, self))
+    }
+}
+
+/// Extracts one column per `(name, extractor)` pair into a `RecordBatch` with
+/// a row per line of `buffer`. Lines where an extractor finds nothing are null.
+///
+/// ```
+/// use analogz::containers::Buffer;
+/// use analogz_extractor::{
+///     arrow::to_record_batch, datetime::DateTimeExtractor, ip_addr::IpAddrExtractor,
+/// };
+///
+/// let logs = Buffer::new("2024-01-15 10:20:30 from 10.0.0.1\nno data".to_string());
+/// let batch = to_record_batch(
+///     &logs,
+///     &[("time", &DateTimeExtractor::default()), ("ip", &IpAddrExtractor::default())],
+/// )
+/// .unwrap();
+/// assert_eq!(batch.num_rows(), 2);
+/// assert_eq!(batch.num_columns(), 2);
+/// ```
+pub fn to_record_batch(
+    buffer: &Buffer,
+    columns: &[(&str, &dyn ArrowExtractor)],
+) -> Result<RecordBatch, ArrowError> {
+    let schema = Schema::new(
+        columns
+            .iter()
+            .map(|(name, ext)| Field::new(*name, ext.data_type(), true))
+            .collect::<Vec<_>>(),
+    );
+    let arrays = columns
+        .iter()
+        .map(|(_, ext)| ext.extract_array(buffer))
+        .collect();
+    RecordBatch::try_new_with_options(
+        Arc::new(schema),
+        arrays,
+        &RecordBatchOptions::new().with_row_count(Some(buffer.len())),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::{Array, cast::AsArray, types::TimestampMicrosecondType};
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::{
+        datetime::DateTimeExtractor, dictionary::DictionaryExtractor, ip_addr::IpAddrExtractor,
+    };
+
+    fn logs() -> Buffer {
+        Buffer::new(
+            [
+                "2024-01-15 10:20:30 INFO request from 10.0.0.1",
+                "WARN timeout without context",
+                "2024-01-15 10:20:32 ERROR OOM on 10.0.0.2",
+            ]
+            .join("\n"),
+        )
+    }
+
+    #[test]
+    fn test_to_record_batch_rows_and_values() {
+        let logs = logs();
+        let category =
+            DictionaryExtractor::new([("OOM", "memory"), ("timeout", "network")]).unwrap();
+        let batch = to_record_batch(
+            &logs,
+            &[
+                ("time", &DateTimeExtractor::default()),
+                ("ip", &IpAddrExtractor::default()),
+                ("category", &category),
+            ],
+        )
+        .unwrap();
+        assert_eq!(batch.num_rows(), logs.len());
+
+        let time = batch
+            .column_by_name("time")
+            .unwrap()
+            .as_primitive::<TimestampMicrosecondType>();
+        let expected = NaiveDate::from_ymd_opt(2024, 1, 15)
+            .unwrap()
+            .and_hms_opt(10, 20, 32)
+            .unwrap();
+        assert_eq!(time.value(2), expected.and_utc().timestamp_micros());
+        assert!(time.is_null(1));
+
+        let ip = batch.column_by_name("ip").unwrap().as_string::<i32>();
+        assert_eq!(ip.value(0), "10.0.0.1");
+        assert!(ip.is_null(1));
+
+        let category = batch.column_by_name("category").unwrap().as_string::<i32>();
+        assert!(category.is_null(0));
+        assert_eq!(category.value(1), "network");
+        assert_eq!(category.value(2), "memory");
+    }
+
+    #[test]
+    fn test_to_record_batch_without_columns() {
+        let batch = to_record_batch(&logs(), &[]).unwrap();
+        assert_eq!((batch.num_rows(), batch.num_columns()), (3, 0));
+    }
+}
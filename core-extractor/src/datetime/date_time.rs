@@ -1,6 +1,6 @@
 use std::{ops::Deref, str::FromStr};
 
-use chrono::NaiveDateTime;
+use chrono::{NaiveDateTime, TimeDelta};
 
 pub const DATETIME_FORMATS: &[&str] = &[
     // Y-
@@ -67,6 +67,11 @@ pub struct DateTimeNotFound;
 #[error("Invalid date time format")]
 pub struct InvalidDateTimeFormat;
 
+/// A date time extracted from a log.
+///
+/// Date times are naive: a time zone or offset in the text is matched by the
+/// format but not kept, so comparisons, `min`/`max` (from `Ord`) and
+/// [`DateTime::duration_since`] compare wall-clock times as written.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct DateTime(NaiveDateTime);
 
@@ -81,6 +86,12 @@ impl DateTime {
         self.0
     }
 
+    /// Returns the time elapsed from `earlier` to `self`, negative if `earlier`
+    /// is actually later.
+    pub fn duration_since(&self, earlier: &DateTime) -> TimeDelta {
+        self.0.signed_duration_since(earlier.0)
+    }
+
     pub fn between(&self, start: Self, end: Self) -> bool {
         start.0 < self.0 && self.0 < end.0
     }
@@ -120,3 +131,26 @@ impl Deref for DateTime {
         &self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duration_since_between_parsed_date_times() {
+        let start: DateTime = "2024-01-15 10:20:30".parse().unwrap();
+        let end: DateTime = "2024-01-15 10:22:05".parse().unwrap();
+        assert_eq!(end.duration_since(&start), TimeDelta::seconds(95));
+        assert_eq!(start.duration_since(&end), TimeDelta::seconds(-95));
+        assert_eq!(start.duration_since(&start), TimeDelta::zero());
+    }
+
+    #[test]
+    fn min_and_max() {
+        let early: DateTime = "2024-01-15 10:20:30".parse().unwrap();
+        let late: DateTime = "15/01/2024 11:00:00".parse().unwrap();
+        assert_eq!(early.min(late), early);
+        assert_eq!(early.max(late), late);
+        assert_eq!([late, early].into_iter().min(), Some(early));
+    }
+}
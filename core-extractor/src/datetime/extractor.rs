@@ -1,15 +1,142 @@
-use itertools::Itertools;
-
-use crate::{containers::DateTime, extractors::Match, misc::token_borders::TokenBorders};
-
-// pub fn extract(haystack: &str) -> impl Iterator<Item = Match<DateTime>> {
-//     haystack.char_indices().map(f)
-//     TokenBorders::new(haystack)
-//         .unique()
-//         .map(|start| (start, &haystack[start..]))
-//         .filter_map(|(start, slice)| {
-//             let (value, rem) = DateTime::parse_and_remainder(slice).ok()?;
-//             let end = rem.as_ptr() as usize - haystack.as_ptr() as usize;
-//             Some(Match { start, end, value })
-//         })
-// }
+use analogz::containers::{ArcStr, Buffer};
+use chrono::NaiveDateTime;
+
+use crate::{
+    datetime::{DATETIME_FORMATS, DateTime},
+    r#match::{Match, token_starts},
+};
+
+/// Extracts the first date time found in a text.
+///
+/// By default every format of [`DATETIME_FORMATS`] is tried at each token
+/// boundary. When the format of a log is known (see
+/// [`DateTimeExtractor::detect_format`]), pinning it with
+/// [`DateTimeExtractor::with_format`] skips the brute force over all formats.
+#[derive(Debug, Clone, Default)]
+pub struct DateTimeExtractor {
+    format: Option<String>,
+}
+
+impl DateTimeExtractor {
+    /// Creates an extractor that only parses date times in the given format.
+    pub fn with_format(fmt: impl Into<String>) -> Self {
+        Self {
+            format: Some(fmt.into()),
+        }
+    }
+
+    pub fn format(&self) -> Option<&str> {
+        self.format.as_deref()
+    }
+
+    pub fn extract(&self, text: ArcStr) -> Option<DateTime> {
+        let haystack = text.as_str();
+        token_starts(haystack).find_map(|start| self.parse_at(haystack, start).map(|(dt, _)| dt))
+    }
+
+    /// Returns every date time found in `text`, with its offsets relative to
+    /// `text`. As with [`date_time`](crate::r#match::date_time), scanning
+    /// resumes after each match, so the matches never overlap.
+    pub fn matches(&self, text: ArcStr) -> impl Iterator<Item = Match<DateTime>> {
+        let mut resume = 0;
+        let starts = token_starts(text.as_str()).collect::<Vec<_>>();
+        starts.into_iter().filter_map(move |start| {
+            if start < resume {
+                return None;
+            }
+            let (value, end) = self.parse_at(text.as_str(), start)?;
+            resume = end;
+            Some(Match::new(start, end, value))
+        })
+    }
+
+    /// Parses a date time at `start` of `haystack`, returning it with the
+    /// offset where it ends.
+    fn parse_at(&self, haystack: &str, start: usize) -> Option<(DateTime, usize)> {
+        let slice = &haystack[start..];
+        let (value, rem) = match &self.format {
+            Some(fmt) => NaiveDateTime::parse_and_remainder(slice, fmt)
+                .ok()
+                .map(|(dt, rem)| (DateTime::from(dt), rem))?,
+            None => DateTime::parse_and_remainder(slice).ok()?,
+        };
+        Some((value, haystack.len() - rem.len()))
+    }
+
+    /// Returns the format of [`DATETIME_FORMATS`] that parses a date time in the
+    /// largest number of lines of `sample`, or `None` if no line contains one.
+    ///
+    /// Ties are broken by the order of [`DATETIME_FORMATS`], which lists the more
+    /// specific formats first. Passing a `Buffer::sample` of a large log is
+    /// usually enough to detect its format.
+    pub fn detect_format(&self, sample: &Buffer) -> Option<&'static str> {
+        let mut counts = vec![0usize; DATETIME_FORMATS.len()];
+        for line in sample.iter() {
+            let haystack = line.as_str();
+            for (count, fmt) in counts.iter_mut().zip(DATETIME_FORMATS) {
+                if token_starts(haystack).any(|start| {
+                    NaiveDateTime::parse_and_remainder(&haystack[start..], fmt).is_ok()
+                }) {
+                    *count += 1;
+                }
+            }
+        }
+        let (idx, count) = counts
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|(_, count)| **count)?;
+        (*count > 0).then_some(DATETIME_FORMATS[idx])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logs() -> Buffer {
+        Buffer::new(
+            [
+                "2024-01-15 10:20:30 INFO service started",
+                "2024-01-15 10:20:31 WARN disk usage at 91%",
+                "no timestamp on this line",
+                "2024-01-15 10:21:02 ERROR connection reset",
+            ]
+            .join("\n"),
+        )
+    }
+
+    #[test]
+    fn detects_consistent_format() {
+        let extractor = DateTimeExtractor::default();
+        assert_eq!(extractor.detect_format(&logs()), Some("%Y-%m-%d %H:%M:%S"));
+    }
+
+    #[test]
+    fn detect_format_returns_none_without_date_times() {
+        let extractor = DateTimeExtractor::default();
+        let buffer = Buffer::new("nothing here\nor here".to_string());
+        assert_eq!(extractor.detect_format(&buffer), None);
+    }
+
+    #[test]
+    fn extract_finds_first_date_time() {
+        let extractor = DateTimeExtractor::default();
+        let got = extractor.extract(ArcStr::from("at 2024-01-15 10:20:30 INFO started"));
+        assert_eq!(got, Some("2024-01-15 10:20:30".parse().unwrap()));
+    }
+
+    #[test]
+    fn with_format_uses_only_the_given_format() {
+        let buffer = logs();
+        let fmt = DateTimeExtractor::default().detect_format(&buffer).unwrap();
+        let extractor = DateTimeExtractor::with_format(fmt);
+        assert_eq!(extractor.format(), Some("%Y-%m-%d %H:%M:%S"));
+
+        let got = extractor.extract(buffer.get(1).unwrap().into_arc_str());
+        assert_eq!(got, Some("2024-01-15 10:20:31".parse().unwrap()));
+
+        let other = extractor.extract(ArcStr::from("15/01/2024 10:20:30 INFO started"));
+        assert_eq!(other, None);
+    }
+}
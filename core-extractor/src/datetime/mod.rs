@@ -0,0 +1,5 @@
+mod date_time;
+mod extractor;
+
+pub use date_time::{DATETIME_FORMATS, DateTime, DateTimeNotFound, InvalidDateTimeFormat};
+pub use extractor::DateTimeExtractor;
@@ -0,0 +1,133 @@
+use aho_corasick::{AhoCorasick, MatchKind};
+use analogz::containers::ArcStr;
+
+use crate::{extractor::Extractor, r#match::Match};
+
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub struct InvalidDictionary(#[from] aho_corasick::BuildError);
+
+/// Categorizes texts using a dictionary of `(keyword, category)` pairs.
+///
+/// All keywords are searched in a single pass. The leftmost keyword found in
+/// the text wins; when several keywords start at the same offset, the one
+/// listed first in the dictionary is used. Keywords are matched as plain
+/// substrings, so `"OOM"` also matches inside `"ROOM"`.
+///
+/// ```
+/// use analogz::containers::ArcStr;
+/// use analogz_extractor::dictionary::DictionaryExtractor;
+///
+/// let ext = DictionaryExtractor::new([("OOM", "memory"), ("timeout", "network")]).unwrap();
+/// let m = ext.extract(ArcStr::from("upstream timeout after 30s")).unwrap();
+/// assert_eq!(m.value().as_str(), "network");
+/// assert_eq!(m.range(), 9..16);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DictionaryExtractor {
+    automaton: AhoCorasick,
+    categories: Vec<ArcStr>,
+}
+
+impl DictionaryExtractor {
+    pub fn new<K, C>(entries: impl IntoIterator<Item = (K, C)>) -> Result<Self, InvalidDictionary>
+    where
+        K: AsRef<str>,
+        C: Into<ArcStr>,
+    {
+        let (keywords, categories): (Vec<K>, Vec<C>) = entries.into_iter().unzip();
+        let automaton = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostFirst)
+            .build(keywords.iter().map(AsRef::as_ref))?;
+        Ok(Self {
+            automaton,
+            categories: categories.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// Returns the number of entries in the dictionary.
+    pub fn len(&self) -> usize {
+        self.categories.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty()
+    }
+
+    /// Returns the category of the first keyword found in `text`, with the
+    /// offsets of that keyword.
+    pub fn extract(&self, text: ArcStr) -> Option<Match<ArcStr>> {
+        let m = self.automaton.find(text.as_str())?;
+        let category = self.categories[m.pattern().as_usize()].clone();
+        Some(Match::new(m.start(), m.end(), category))
+    }
+
+    /// Returns the categories of every keyword found in `text`, with the
+    /// offsets of the keywords. The keywords found never overlap.
+    pub fn matches(&self, text: ArcStr) -> impl Iterator<Item = Match<ArcStr>> {
+        self.automaton
+            .find_iter(text.as_str())
+            .map(|m| {
+                let category = self.categories[m.pattern().as_usize()].clone();
+                Match::new(m.start(), m.end(), category)
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+impl Extractor for DictionaryExtractor {
+    type Output = Match<ArcStr>;
+
+    fn extract(&self, text: ArcStr) -> Option<Self::Output> {
+        DictionaryExtractor::extract(self, text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extractor() -> DictionaryExtractor {
+        DictionaryExtractor::new([
+            ("OOM", "memory"),
+            ("timeout", "network"),
+            ("time", "clock"),
+            ("disk full", "storage"),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_extract_second_entry() {
+        let line = ArcStr::from("2024-01-15 ERROR request timeout on /api");
+        let m = extractor().extract(line.clone()).unwrap();
+        assert_eq!(m.value().as_str(), "network");
+        assert_eq!(m.slice_of(&line), "timeout");
+    }
+
+    #[test]
+    fn test_extract_leftmost_keyword_wins() {
+        let m = extractor()
+            .extract(ArcStr::from("disk full, then OOM"))
+            .unwrap();
+        assert_eq!(m.value().as_str(), "storage");
+        assert_eq!(m.range(), 0..9);
+    }
+
+    #[test]
+    fn test_extract_offsets_are_relative_to_text() {
+        let line = ArcStr::from("xx killed by OOM").slice(3..);
+        let m = extractor().extract(line.clone()).unwrap();
+        assert_eq!(m.range(), 10..13);
+        assert_eq!(m.slice_of(&line), "OOM");
+    }
+
+    #[test]
+    fn test_extract_no_match() {
+        assert!(extractor().extract(ArcStr::from("all good")).is_none());
+        let empty = DictionaryExtractor::new(Vec::<(&str, &str)>::new()).unwrap();
+        assert!(empty.is_empty());
+        assert!(empty.extract(ArcStr::from("OOM")).is_none());
+    }
+}
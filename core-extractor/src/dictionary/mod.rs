@@ -0,0 +1,3 @@
+mod extractor;
+
+pub use extractor::{DictionaryExtractor, InvalidDictionary};
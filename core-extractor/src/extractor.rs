@@ -0,0 +1,308 @@
+use std::{any::Any, ops::Range};
+
+use analogz::containers::{ArcSlice, ArcStr, Buffer};
+
+use crate::{
+    datetime::{DateTime, DateTimeExtractor},
+    dictionary::DictionaryExtractor,
+    ip_addr::{IpAddr, IpAddrExtractor},
+    socket_addr::{SocketAddr, SocketAddrExtractor},
+};
+
+/// Extracts a value from a piece of text, typically a log line.
+pub trait Extractor {
+    type Output;
+
+    fn extract(&self, text: ArcStr) -> Option<Self::Output>;
+
+    /// Returns an extractor that tries `self` first and falls back to `other`
+    /// when `self` finds nothing.
+    ///
+    /// ```
+    /// use analogz::containers::ArcStr;
+    /// use analogz_extractor::{
+    ///     extractor::Extractor, ip_addr::IpAddrExtractor, socket_addr::SocketAddrExtractor,
+    /// };
+    ///
+    /// let ip = IpAddrExtractor::default()
+    ///     .map(|ip| ip.into_inner())
+    ///     .or(SocketAddrExtractor::default().map(|addr| addr.ip()));
+    /// let found = ip.extract(ArcStr::from("connect to 10.0.0.1:8080")).unwrap();
+    /// assert_eq!(found.to_string(), "10.0.0.1");
+    /// ```
+    fn or<E>(self, other: E) -> Or<Self, E>
+    where
+        Self: Sized,
+        E: Extractor<Output = Self::Output>,
+    {
+        Or {
+            first: self,
+            second: other,
+        }
+    }
+
+    /// Returns an extractor that transforms the values found by `self` with `f`.
+    fn map<F, O>(self, f: F) -> Map<Self, F>
+    where
+        Self: Sized,
+        F: Fn(Self::Output) -> O,
+    {
+        Map { inner: self, f }
+    }
+}
+
+/// Extractor falling back to a second extractor, created by [`Extractor::or`].
+#[derive(Debug, Clone)]
+pub struct Or<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> Extractor for Or<A, B>
+where
+    A: Extractor,
+    B: Extractor<Output = A::Output>,
+{
+    type Output = A::Output;
+
+    fn extract(&self, text: ArcStr) -> Option<Self::Output> {
+        self.first
+            .extract(text.clone())
+            .or_else(|| self.second.extract(text))
+    }
+}
+
+/// Extractor transforming the values of another extractor, created by
+/// [`Extractor::map`].
+#[derive(Debug, Clone)]
+pub struct Map<E, F> {
+    inner: E,
+    f: F,
+}
+
+impl<E, F, O> Extractor for Map<E, F>
+where
+    E: Extractor,
+    F: Fn(E::Output) -> O,
+{
+    type Output = O;
+
+    fn extract(&self, text: ArcStr) -> Option<Self::Output> {
+        self.inner.extract(text).map(&self.f)
+    }
+}
+
+impl Extractor for DateTimeExtractor {
+    type Output = DateTime;
+
+    fn extract(&self, text: ArcStr) -> Option<Self::Output> {
+        DateTimeExtractor::extract(self, text)
+    }
+}
+
+impl Extractor for IpAddrExtractor {
+    type Output = IpAddr;
+
+    fn extract(&self, text: ArcStr) -> Option<Self::Output> {
+        IpAddrExtractor::extract(self, text)
+    }
+}
+
+impl Extractor for SocketAddrExtractor {
+    type Output = SocketAddr;
+
+    fn extract(&self, text: ArcStr) -> Option<Self::Output> {
+        SocketAddrExtractor::extract(self, text)
+    }
+}
+
+/// Object-safe form of [`Extractor`], so extractors with different outputs can
+/// be kept together, e.g. in a `Vec<Box<dyn DynExtractor>>`.
+///
+/// It is implemented for every `Extractor` whose output is `'static`. The
+/// extracted value is boxed as `dyn Any` and can be recovered with
+/// `downcast_ref`/`downcast`.
+///
+/// ```
+/// use analogz::containers::ArcStr;
+/// use analogz_extractor::{
+///     datetime::{DateTime, DateTimeExtractor},
+///     extractor::DynExtractor,
+///     ip_addr::{IpAddr, IpAddrExtractor},
+/// };
+///
+/// let extractors: Vec<Box<dyn DynExtractor>> = vec![
+///     Box::new(IpAddrExtractor::default()),
+///     Box::new(DateTimeExtractor::default()),
+/// ];
+/// let line = ArcStr::from("2024-01-15 10:20:30 from 10.0.0.1");
+/// let values = extractors
+///     .iter()
+///     .map(|ext| ext.extract_dyn(line.clone()).unwrap())
+///     .collect::<Vec<_>>();
+/// assert!(values[0].downcast_ref::<IpAddr>().is_some());
+/// assert!(values[1].downcast_ref::<DateTime>().is_some());
+/// ```
+pub trait DynExtractor: Send + Sync {
+    fn extract_dyn(&self, text: ArcStr) -> Option<Box<dyn Any + Send>>;
+
+    /// Returns the name of the type of the extracted values.
+    fn output_type_name(&self) -> &'static str;
+}
+
+impl<E> DynExtractor for E
+where
+    E: Extractor + Send + Sync,
+    E::Output: Any + Send,
+{
+    fn extract_dyn(&self, text: ArcStr) -> Option<Box<dyn Any + Send>> {
+        self.extract(text)
+            .map(|value| Box::new(value) as Box<dyn Any + Send>)
+    }
+
+    fn output_type_name(&self) -> &'static str {
+        std::any::type_name::<E::Output>()
+    }
+}
+
+/// An extractor that can locate every value it finds in a text, e.g. to
+/// [`redact`](crate::redact::redact) them.
+///
+/// Unlike [`Extractor`], it is object safe and does not expose the values, so
+/// extractors with different outputs can be used together.
+pub trait SpanExtractor: Send + Sync {
+    /// Returns the byte ranges, relative to `text`, of the values found in it.
+    fn spans(&self, text: ArcStr) -> Vec<Range<usize>>;
+}
+
+impl SpanExtractor for DateTimeExtractor {
+    fn spans(&self, text: ArcStr) -> Vec<Range<usize>> {
+        self.matches(text).map(|m| m.range()).collect()
+    }
+}
+
+impl SpanExtractor for DictionaryExtractor {
+    fn spans(&self, text: ArcStr) -> Vec<Range<usize>> {
+        self.matches(text).map(|m| m.range()).collect()
+    }
+}
+
+impl SpanExtractor for IpAddrExtractor {
+    fn spans(&self, text: ArcStr) -> Vec<Range<usize>> {
+        self.matches(text).map(|m| m.range()).collect()
+    }
+}
+
+impl SpanExtractor for SocketAddrExtractor {
+    fn spans(&self, text: ArcStr) -> Vec<Range<usize>> {
+        self.matches(text).map(|m| m.range()).collect()
+    }
+}
+
+/// Runs `ext` over every text, keeping `None` where nothing was extracted so
+/// the result is aligned with the input.
+pub fn extract_values<E>(iter: impl IntoIterator<Item = ArcStr>, ext: &E) -> Vec<Option<E::Output>>
+where
+    E: Extractor,
+{
+    iter.into_iter().map(|text| ext.extract(text)).collect()
+}
+
+/// Runs `ext` over the lines of `buffer` in parallel, keeping `None` where
+/// nothing was extracted so the result is aligned with the lines.
+///
+/// ```
+/// use analogz::containers::Buffer;
+/// use analogz_extractor::{extractor::par_extract_values, ip_addr::IpAddrExtractor};
+///
+/// let logs = Buffer::new("from 10.0.0.1\nno address\nto ::1".to_string());
+/// let ips = par_extract_values(&logs, &IpAddrExtractor::default());
+/// assert_eq!(ips[0].as_ref().unwrap().to_string(), "10.0.0.1");
+/// assert!(ips[1].is_none());
+/// ```
+pub fn par_extract_values<E>(buffer: &Buffer, ext: &E) -> ArcSlice<Option<E::Output>>
+where
+    E: Extractor + Sync,
+    E::Output: Send,
+{
+    buffer.par_map(|line| ext.extract(line.into_arc_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn par_extract_values_matches_sequential() {
+        let lines = (0..500)
+            .map(|i| match i % 3 {
+                0 => format!(
+                    "2024-01-15 10:{:02}:00 request from 10.0.{}.1",
+                    i % 60,
+                    i % 250
+                ),
+                1 => "no date here".to_string(),
+                _ => format!("at 15/01/2024 11:{:02}:30 done", i % 60),
+            })
+            .collect::<Vec<_>>();
+        let buffer = Buffer::new(lines.join("\n"));
+        let ext = DateTimeExtractor::default();
+
+        let parallel = par_extract_values(&buffer, &ext);
+        let sequential = extract_values(buffer.iter().map(|l| l.into_arc_str()), &ext);
+
+        assert_eq!(parallel.as_slice(), sequential.as_slice());
+        assert_eq!(parallel.iter().filter(|v| v.is_some()).count(), 333);
+    }
+
+    #[test]
+    fn or_falls_back_when_first_fails() {
+        let ip = IpAddrExtractor::default()
+            .map(|ip| ip.into_inner())
+            .or(SocketAddrExtractor::default().map(|addr| addr.ip()));
+
+        let text = ArcStr::from("upstream 192.168.1.10:443 refused");
+        assert!(IpAddrExtractor::default().extract(text.clone()).is_none());
+        assert_eq!(ip.extract(text).unwrap().to_string(), "192.168.1.10");
+
+        let text = ArcStr::from("client 10.1.2.3 connected");
+        assert_eq!(ip.extract(text).unwrap().to_string(), "10.1.2.3");
+        assert!(ip.extract(ArcStr::from("no address")).is_none());
+    }
+
+    #[test]
+    fn map_transforms_output() {
+        let year = DateTimeExtractor::default().map(|dt| dt.to_string()[..4].to_string());
+        let found = Extractor::extract(&year, ArcStr::from("at 2024-01-15 10:20:30"));
+        assert_eq!(found.as_deref(), Some("2024"));
+    }
+
+    #[test]
+    fn dyn_extractors_run_over_a_line() {
+        let extractors: Vec<Box<dyn DynExtractor>> = vec![
+            Box::new(IpAddrExtractor::default()),
+            Box::new(DateTimeExtractor::default()),
+            Box::new(SocketAddrExtractor::default()),
+        ];
+        let line = ArcStr::from("2024-01-15 10:20:30 request from 10.0.0.1");
+        let values = extractors
+            .iter()
+            .map(|ext| ext.extract_dyn(line.clone()))
+            .collect::<Vec<_>>();
+
+        let ip = values[0]
+            .as_ref()
+            .unwrap()
+            .downcast_ref::<IpAddr>()
+            .unwrap();
+        assert_eq!(ip.to_string(), "10.0.0.1");
+        let dt = values[1]
+            .as_ref()
+            .unwrap()
+            .downcast_ref::<DateTime>()
+            .unwrap();
+        assert_eq!(*dt, DateTimeExtractor::default().extract(line).unwrap());
+        assert!(values[2].is_none());
+        assert!(extractors[0].output_type_name().ends_with("IpAddr"));
+    }
+}
@@ -1,13 +1,31 @@
-use crate::containers::{ArcStr, IpAddr};
+use analogz::containers::ArcStr;
+
+use crate::{ip_addr::IpAddr, r#match::Match};
+
+const DELIMITERS: &[char] = &[
+    ' ', '"', '$', '\'', '(', ')', ',', ';', '<', '>', '@', '[', ']', '`', '{', '|', '}', '=', '-',
+    '_',
+];
 
 #[derive(Debug, Clone, Default)]
 pub struct IpAddrExtractor {}
 
 impl IpAddrExtractor {
     pub fn extract(&self, text: ArcStr) -> Option<IpAddr> {
-        text.split(" \"$'(),;<>@[]`{|}=-_")
+        text.split(DELIMITERS)
             .find_map(|slice| slice.parse::<IpAddr>().ok())
     }
+
+    /// Returns every address found in `text`, with its offsets relative to
+    /// `text`.
+    pub fn matches(&self, text: ArcStr) -> impl Iterator<Item = Match<IpAddr>> {
+        let offset = text.start();
+        text.split(DELIMITERS).filter_map(move |slice| {
+            let value = slice.parse::<IpAddr>().ok()?;
+            let start = slice.start() - offset;
+            Some(Match::new(start, start + slice.len(), value))
+        })
+    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,11 @@
-use std::{ops::Deref, str::FromStr};
+use std::{fmt::Display, ops::Deref, str::FromStr};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct IpAddr(std::net::IpAddr);
 
 impl IpAddr {
@@ -17,6 +22,12 @@ impl FromStr for IpAddr {
     }
 }
 
+impl Display for IpAddr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
 impl Deref for IpAddr {
     type Target = std::net::IpAddr;
 
@@ -24,3 +35,18 @@ impl Deref for IpAddr {
         &self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_matches_std() {
+        for s in ["192.168.1.1", "::1", "2001:db8::8a2e:370:7334"] {
+            let addr = s.parse::<IpAddr>().unwrap();
+            let std_addr = s.parse::<std::net::IpAddr>().unwrap();
+            assert_eq!(format!("{addr}"), format!("{std_addr}"));
+            assert_eq!(addr.to_string(), s);
+        }
+    }
+}
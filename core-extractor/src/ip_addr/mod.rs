@@ -0,0 +1,6 @@
+mod extractor;
+#[allow(clippy::module_inception)]
+mod ip_addr;
+
+pub use extractor::IpAddrExtractor;
+pub use ip_addr::IpAddr;
@@ -0,0 +1,126 @@
+use analogz::containers::ArcStr;
+use serde_json::Value;
+
+use crate::{extractor::Extractor, r#match::Match};
+
+/// Extracts the first JSON object or array embedded in a text, e.g. the
+/// `{"k":1}` of `... context={"k":1}`.
+///
+/// Candidates start at each `{` or `[` and end at the bracket that balances
+/// it; brackets inside JSON strings are ignored. The first candidate that
+/// parses as JSON is returned, with offsets bounding the JSON text.
+///
+/// ```
+/// use analogz::containers::ArcStr;
+/// use analogz_extractor::json::JsonExtractor;
+///
+/// let line = ArcStr::from(r#"INFO request done context={"status":200}"#);
+/// let m = JsonExtractor::default().extract(line.clone()).unwrap();
+/// assert_eq!(m.value()["status"], 200);
+/// assert_eq!(m.slice_of(&line), r#"{"status":200}"#);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct JsonExtractor {}
+
+impl JsonExtractor {
+    pub fn extract(&self, text: ArcStr) -> Option<Match<Value>> {
+        let haystack = text.as_str();
+        haystack
+            .bytes()
+            .enumerate()
+            .filter(|(_, b)| matches!(b, b'{' | b'['))
+            .find_map(|(start, _)| {
+                let end = balanced_end(&haystack.as_bytes()[start..])? + start;
+                let value = serde_json::from_str(&haystack[start..end]).ok()?;
+                Some(Match::new(start, end, value))
+            })
+    }
+}
+
+impl Extractor for JsonExtractor {
+    type Output = Match<Value>;
+
+    fn extract(&self, text: ArcStr) -> Option<Self::Output> {
+        JsonExtractor::extract(self, text)
+    }
+}
+
+/// Returns the length of the bracketed span `bytes` starts with, or `None` if
+/// its brackets are unbalanced or mismatched.
+fn balanced_end(bytes: &[u8]) -> Option<usize> {
+    let mut stack = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, b) in bytes.iter().enumerate() {
+        if in_string {
+            match b {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match b {
+            b'"' => in_string = true,
+            b'{' => stack.push(b'}'),
+            b'[' => stack.push(b']'),
+            b'}' | b']' => {
+                if stack.pop() != Some(*b) {
+                    return None;
+                }
+                if stack.is_empty() {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_extract_nested_object() {
+        let line = ArcStr::from(
+            r#"2024-01-15 WARN retry context={"req":{"id":7,"tags":["a","b"]},"ok":false} tail"#,
+        );
+        let m = JsonExtractor::default().extract(line.clone()).unwrap();
+        assert_eq!(
+            *m.value(),
+            json!({"req": {"id": 7, "tags": ["a", "b"]}, "ok": false})
+        );
+        assert_eq!(
+            m.slice_of(&line),
+            r#"{"req":{"id":7,"tags":["a","b"]},"ok":false}"#
+        );
+    }
+
+    #[test]
+    fn test_extract_brackets_inside_strings() {
+        let line = ArcStr::from(r#"msg={"text":"a } in \"quotes\" and [ here"}"#);
+        let m = JsonExtractor::default().extract(line).unwrap();
+        assert_eq!(m.value()["text"], r#"a } in "quotes" and [ here"#);
+    }
+
+    #[test]
+    fn test_extract_skips_non_json_brackets() {
+        let line = ArcStr::from(r#"[main] {not json} payload=[1,2,3]"#);
+        let m = JsonExtractor::default().extract(line).unwrap();
+        assert_eq!(*m.value(), json!([1, 2, 3]));
+        assert_eq!(m.range(), 26..33);
+    }
+
+    #[test]
+    fn test_extract_none_when_unbalanced() {
+        let ext = JsonExtractor::default();
+        assert!(ext.extract(ArcStr::from(r#"cut off {"k":[1,2"#)).is_none());
+        assert!(ext.extract(ArcStr::from(r#"mismatched {"k":1]"#)).is_none());
+        assert!(ext.extract(ArcStr::from("no json here")).is_none());
+    }
+}
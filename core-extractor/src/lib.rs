@@ -1,14 +1,13 @@
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod datetime;
+pub mod dictionary;
+pub mod extractor;
+pub mod ip_addr;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod r#match;
+pub mod prelude;
+pub mod redact;
+pub mod schema;
+pub mod socket_addr;
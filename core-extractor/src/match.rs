@@ -1,4 +1,13 @@
-#[derive(Debug)]
+use std::ops::Range;
+
+use analogz::{containers::ArcStr, misc::token_borders::is_separator_byte};
+
+use crate::datetime::DateTime;
+
+/// A value extracted from a haystack along with the byte range it spans.
+///
+/// Offsets are relative to the start of the haystack that was searched.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Match<T> {
     start: usize,
     end: usize,
@@ -6,6 +15,10 @@ pub struct Match<T> {
 }
 
 impl<T> Match<T> {
+    pub fn new(start: usize, end: usize, value: T) -> Self {
+        Self { start, end, value }
+    }
+
     #[inline]
     pub fn start(&self) -> usize {
         self.start
@@ -25,13 +38,56 @@ impl<T> Match<T> {
     pub fn into_value(self) -> T {
         self.value
     }
+
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Returns the part of `haystack` covered by this match. `haystack` should be
+    /// the `ArcStr` the match was extracted from.
+    pub fn slice_of(&self, haystack: &ArcStr) -> ArcStr {
+        haystack.slice(self.range())
+    }
+}
+
+impl<T> From<Match<T>> for Range<usize> {
+    fn from(value: Match<T>) -> Self {
+        value.range()
+    }
+}
+
+/// Returns the byte offsets where a token may start: the beginning of the
+/// haystack and every position right after an ASCII whitespace or punctuation.
+pub(crate) fn token_starts(haystack: &str) -> impl Iterator<Item = usize> {
+    std::iter::once(0).chain(
+        haystack
+            .bytes()
+            .enumerate()
+            .filter_map(|(i, b)| is_separator_byte(b).then_some(i + 1)),
+    )
 }
 
+/// Returns the date times found in `haystack`.
+///
+/// Candidates are anchored at token boundaries and, once a date time is
+/// matched, scanning resumes after its end, so the returned matches never
+/// overlap. Use [`date_time_overlapping`] to get every candidate.
 pub fn date_time(haystack: &str) -> impl Iterator<Item = Match<DateTime>> {
-    std::iter::once(0)
-        .chain(haystack.char_indices().filter_map(|(i, c)| {
-            (c.is_ascii_whitespace() || c.is_ascii_punctuation()).then_some(i + 1)
-        }))
+    let mut resume = 0;
+    date_time_overlapping(haystack).filter(move |m| {
+        let keep = m.start >= resume;
+        if keep {
+            resume = m.end;
+        }
+        keep
+    })
+}
+
+/// Returns every date time that can be parsed starting at a token boundary of
+/// `haystack`, including candidates that overlap a previous match.
+pub fn date_time_overlapping(haystack: &str) -> impl Iterator<Item = Match<DateTime>> {
+    token_starts(haystack)
         .map(|start| (start, &haystack[start..]))
         .filter_map(|(start, slice)| {
             let (value, rem) = DateTime::parse_and_remainder(slice).ok()?;
@@ -39,3 +95,55 @@ pub fn date_time(haystack: &str) -> impl Iterator<Item = Match<DateTime>> {
             Some(Match { start, end, value })
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_matches_start_and_end() {
+        let m = Match::new(3, 8, ());
+        assert_eq!(m.range(), m.start()..m.end());
+        assert_eq!(Range::from(m), 3..8);
+    }
+
+    #[test]
+    fn slice_of_returns_matched_text() {
+        let line = ArcStr::from("at 2024-01-15 10:20:30 INFO started");
+        let m = date_time(line.as_str()).next().unwrap();
+        assert_eq!(m.slice_of(&line), "2024-01-15 10:20:30");
+        assert_eq!(m.clone().into_value(), *m.value());
+    }
+
+    #[test]
+    fn date_time_skips_overlapping_candidates() {
+        let haystack = "2024 Jan 15 10:20:30 2024 done";
+        let ranges = date_time(haystack).map(|m| m.range()).collect::<Vec<_>>();
+        assert_eq!(ranges, vec![0..20]);
+    }
+
+    #[test]
+    fn date_time_overlapping_yields_every_candidate() {
+        let haystack = "2024 Jan 15 10:20:30 2024 done";
+        let ranges = date_time_overlapping(haystack)
+            .map(|m| m.range())
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, vec![0..20, 5..25]);
+    }
+
+    #[test]
+    fn date_time_keeps_disjoint_matches() {
+        let haystack = "2024-01-15 10:20:30 to 2024-01-16 11:00:00";
+        assert_eq!(date_time(haystack).count(), 2);
+    }
+
+    #[test]
+    fn slice_of_sub_slice_is_relative() {
+        let base = ArcStr::from("xxxx|2024-01-15 10:20:30");
+        let line = base.slice(5..);
+        let m = date_time(line.as_str()).next().unwrap();
+        let slice = m.slice_of(&line);
+        assert_eq!(slice, "2024-01-15 10:20:30");
+        assert_eq!(slice.start(), 5);
+    }
+}
@@ -0,0 +1,25 @@
+//! The extractors and the commonly used types of analogz, for a glob import.
+//!
+//! ```
+//! use analogz_extractor::prelude::*;
+//!
+//! let logs = Buffer::new("from 10.0.0.1\nno address".to_string());
+//! let ips = par_extract_values(&logs, &IpAddrExtractor::default());
+//! assert_eq!(ips.iter().flatten().count(), 1);
+//! ```
+
+pub use analogz::prelude::*;
+
+pub use crate::{
+    datetime::{DateTime, DateTimeExtractor},
+    dictionary::DictionaryExtractor,
+    extractor::{DynExtractor, Extractor, SpanExtractor, extract_values, par_extract_values},
+    ip_addr::{IpAddr, IpAddrExtractor},
+    r#match::Match,
+    redact::{Redact, redact},
+    schema::{FieldType, FieldValue, Record, SchemaExtractor},
+    socket_addr::{SocketAddr, SocketAddrExtractor},
+};
+
+#[cfg(feature = "json")]
+pub use crate::json::JsonExtractor;
@@ -0,0 +1,170 @@
+use std::ops::Range;
+
+use analogz::containers::Buffer;
+
+use crate::extractor::SpanExtractor;
+
+#[derive(Debug, thiserror::Error)]
+#[error("the replacement {0:?} contains a newline")]
+pub struct MultilineReplacement(pub String);
+
+/// Returns a copy of `buffer` where every span found by `extractors` is
+/// replaced with `replacement`.
+///
+/// Lines are redacted in parallel. When spans overlap, within one extractor
+/// or across several, their union is replaced once; spans that only touch are
+/// replaced one by one. The redacted buffer has the same lines as `buffer`,
+/// so columns computed on one line up with the other.
+///
+/// # Errors
+///
+/// Fails with [`MultilineReplacement`] if `replacement` contains a `\n`, as
+/// it would split the redacted lines.
+///
+/// ```
+/// use analogz::containers::Buffer;
+/// use analogz_extractor::{ip_addr::IpAddrExtractor, redact::redact};
+///
+/// let logs = Buffer::new("from 10.0.0.1 to 10.0.0.2\nno address".to_string());
+/// let redacted = redact(&logs, &[&IpAddrExtractor::default()], "***").unwrap();
+/// assert_eq!(redacted.get(0).unwrap().as_str(), "from *** to ***");
+/// ```
+pub fn redact(
+    buffer: &Buffer,
+    extractors: &[&dyn SpanExtractor],
+    replacement: &str,
+) -> Result<Buffer, MultilineReplacement> {
+    if replacement.contains('\n') {
+        return Err(MultilineReplacement(replacement.to_string()));
+    }
+    let lines = buffer.par_map(|line| {
+        let text = line.into_arc_str();
+        let spans = extractors
+            .iter()
+            .flat_map(|ext| ext.spans(text.clone()))
+            .collect();
+        let mut redacted = String::with_capacity(text.len());
+        let mut last = 0;
+        for span in merge_spans(spans) {
+            redacted.push_str(&text.as_str()[last..span.start]);
+            redacted.push_str(replacement);
+            last = span.end;
+        }
+        redacted.push_str(&text.as_str()[last..]);
+        redacted
+    });
+    Ok(Buffer::from_lines(lines.iter()))
+}
+
+/// Redaction of the values found by extractors, as a method of [`Buffer`].
+///
+/// ```
+/// use analogz::containers::Buffer;
+/// use analogz_extractor::{ip_addr::IpAddrExtractor, redact::Redact};
+///
+/// let logs = Buffer::new("from 10.0.0.1".to_string());
+/// let redacted = logs.redact(&[&IpAddrExtractor::default()], "***").unwrap();
+/// assert_eq!(redacted.get(0).unwrap().as_str(), "from ***");
+/// ```
+pub trait Redact {
+    /// See [`redact`].
+    fn redact(
+        &self,
+        extractors: &[&dyn SpanExtractor],
+        replacement: &str,
+    ) -> Result<Buffer, MultilineReplacement>;
+}
+
+impl Redact for Buffer {
+    fn redact(
+        &self,
+        extractors: &[&dyn SpanExtractor],
+        replacement: &str,
+    ) -> Result<Buffer, MultilineReplacement> {
+        redact(self, extractors, replacement)
+    }
+}
+
+/// Sorts `spans` and merges the ones that overlap.
+fn merge_spans(mut spans: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    spans.retain(|span| !span.is_empty());
+    spans.sort_unstable_by_key(|span| span.start);
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(spans.len());
+    for span in spans {
+        match merged.last_mut() {
+            Some(last) if span.start < last.end => last.end = last.end.max(span.end),
+            _ => merged.push(span),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dictionary::DictionaryExtractor, ip_addr::IpAddrExtractor};
+
+    #[test]
+    fn redacts_ips_and_keeps_other_text() {
+        let buffer = Buffer::new(
+            [
+                "accepted 10.0.0.1 port 22",
+                "no address here",
+                "[192.168.1.7] -> ::1 closed",
+            ]
+            .join("\n"),
+        );
+        let redacted = redact(&buffer, &[&IpAddrExtractor::default()], "***").unwrap();
+        assert_eq!(
+            redacted.iter().map(|l| l.to_string()).collect::<Vec<_>>(),
+            vec![
+                "accepted *** port 22",
+                "no address here",
+                "[***] -> *** closed"
+            ]
+        );
+    }
+
+    #[test]
+    fn overlapping_spans_are_replaced_once() {
+        let buffer = Buffer::new("host 10.0.0.1 down".to_string());
+        let dictionary = DictionaryExtractor::new([("0.0.1 d", "x")]).unwrap();
+        let redacted = redact(
+            &buffer,
+            &[&IpAddrExtractor::default(), &dictionary],
+            "<redacted>",
+        )
+        .unwrap();
+        assert_eq!(redacted.get(0).unwrap().as_str(), "host <redacted>own");
+    }
+
+    #[test]
+    fn touching_spans_are_replaced_separately() {
+        let buffer = Buffer::new("peer 10.0.0.1:22 up".to_string());
+        let ip = DictionaryExtractor::new([("10.0.0.1", "ip")]).unwrap();
+        let port = DictionaryExtractor::new([(":22", "port")]).unwrap();
+        let redacted = buffer.redact(&[&ip, &port], "[R]").unwrap();
+        assert_eq!(redacted.get(0).unwrap().as_str(), "peer [R][R] up");
+    }
+
+    #[test]
+    fn newline_in_replacement_is_rejected() {
+        let buffer = Buffer::new("from 10.0.0.1\nto 10.0.0.2".to_string());
+        let err = buffer
+            .redact(&[&IpAddrExtractor::default()], "a\nb")
+            .unwrap_err();
+        assert_eq!(err.0, "a\nb");
+        let redacted = buffer
+            .redact(&[&IpAddrExtractor::default()], "a\rb")
+            .unwrap();
+        assert_eq!(redacted.len(), buffer.len());
+    }
+
+    #[test]
+    fn merge_spans_unions_overlapping_only() {
+        assert_eq!(
+            merge_spans(vec![8..10, 0..3, 2..5, 5..6, 7..7]),
+            vec![0..5, 5..6, 8..10]
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use std::sync::Arc;
+
+use analogz::containers::{ArcStr, Regex};
+
+use crate::{
+    extractor::Extractor,
+    schema::{FieldValue, Record},
+};
+
+/// The type a captured group is parsed into by a [`SchemaExtractor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FieldType {
+    Str,
+    U64,
+    I64,
+    F64,
+    DateTime,
+    IpAddr,
+    SocketAddr,
+}
+
+impl FieldType {
+    fn parse(self, text: ArcStr) -> Option<FieldValue> {
+        Some(match self {
+            FieldType::Str => FieldValue::Str(text),
+            FieldType::U64 => FieldValue::U64(text.parse().ok()?),
+            FieldType::I64 => FieldValue::I64(text.parse().ok()?),
+            FieldType::F64 => FieldValue::F64(text.parse().ok()?),
+            FieldType::DateTime => FieldValue::DateTime(text.parse().ok()?),
+            FieldType::IpAddr => FieldValue::IpAddr(text.parse().ok()?),
+            FieldType::SocketAddr => FieldValue::SocketAddr(text.parse().ok()?),
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("the regex has no group named {0:?}")]
+pub struct UnknownGroup(pub String);
+
+/// Extracts typed fields from the named groups of a regex.
+///
+/// Each field of the schema names a group of the regex and the type its text
+/// is parsed into. A text the regex does not match yields no record; a group
+/// that did not participate in the match or fails to parse yields a `None`
+/// field.
+///
+/// ```
+/// use analogz::containers::{ArcStr, Regex};
+/// use analogz_extractor::schema::{FieldType, FieldValue, SchemaExtractor};
+///
+/// let re = Regex::new(r"^(?<ip>\S+) (?<bytes>\S+)$").unwrap();
+/// let ext = SchemaExtractor::new(re, &[("ip", FieldType::IpAddr), ("bytes", FieldType::U64)])
+///     .unwrap();
+/// let record = ext.extract(ArcStr::from("10.0.0.1 -")).unwrap();
+/// assert!(matches!(record.get("ip"), Some(FieldValue::IpAddr(_))));
+/// assert_eq!(record.get("bytes"), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SchemaExtractor {
+    re: Regex,
+    names: Arc<[String]>,
+    fields: Vec<(usize, FieldType)>,
+}
+
+impl SchemaExtractor {
+    pub fn new(re: Regex, fields: &[(&str, FieldType)]) -> Result<Self, UnknownGroup> {
+        let groups = fields
+            .iter()
+            .map(|(name, ty)| {
+                let group = re
+                    .capture_names()
+                    .position(|n| n == Some(name))
+                    .ok_or_else(|| UnknownGroup(name.to_string()))?;
+                Ok((group, *ty))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            re,
+            names: fields.iter().map(|(name, _)| name.to_string()).collect(),
+            fields: groups,
+        })
+    }
+
+    pub fn extract(&self, text: ArcStr) -> Option<Record> {
+        let caps = self.re.captures(text.as_str())?;
+        let values = self
+            .fields
+            .iter()
+            .map(|(group, ty)| {
+                let m = caps.get(*group)?;
+                ty.parse(text.slice(m.start()..m.end()))
+            })
+            .collect();
+        Some(Record::new(self.names.clone(), values))
+    }
+}
+
+impl Extractor for SchemaExtractor {
+    type Output = Record;
+
+    fn extract(&self, text: ArcStr) -> Option<Self::Output> {
+        SchemaExtractor::extract(self, text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn access_log() -> SchemaExtractor {
+        let re = Regex::new(
+            r#"^(?<ip>\S+) \[(?<ts>[^\]]+)\] "(?<method>\w+) (?<path>\S+)" (?<status>\d{3}) (?<bytes>\S+)$"#,
+        )
+        .unwrap();
+        SchemaExtractor::new(
+            re,
+            &[
+                ("ts", FieldType::DateTime),
+                ("ip", FieldType::IpAddr),
+                ("path", FieldType::Str),
+                ("status", FieldType::U64),
+                ("bytes", FieldType::U64),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn extracts_typed_record() {
+        let line = ArcStr::from(r#"10.1.2.3 [2024-01-15 10:20:30] "GET /index.html" 200 5120"#);
+        let record = access_log().extract(line).unwrap();
+
+        assert_eq!(record.len(), 5);
+        assert_eq!(
+            record.get("ts"),
+            Some(&FieldValue::DateTime(
+                "2024-01-15 10:20:30".parse().unwrap()
+            ))
+        );
+        assert_eq!(
+            record.get("ip"),
+            Some(&FieldValue::IpAddr("10.1.2.3".parse().unwrap()))
+        );
+        assert_eq!(
+            record.get("path"),
+            Some(&FieldValue::Str(ArcStr::from("/index.html")))
+        );
+        assert_eq!(record.get("status"), Some(&FieldValue::U64(200)));
+        assert_eq!(record.get("bytes"), Some(&FieldValue::U64(5120)));
+        assert_eq!(
+            record.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            ["ts", "ip", "path", "status", "bytes"]
+        );
+    }
+
+    #[test]
+    fn unparseable_groups_are_none() {
+        let ext = access_log();
+        let line = ArcStr::from(r#"localhost [yesterday] "GET /" 404 -"#);
+        let record = ext.extract(line).unwrap();
+        assert_eq!(record.get("ip"), None);
+        assert_eq!(record.get("ts"), None);
+        assert_eq!(record.get("bytes"), None);
+        assert_eq!(record.get("status"), Some(&FieldValue::U64(404)));
+        assert_eq!(record.get("method"), None);
+
+        assert!(ext.extract(ArcStr::from("not an access log")).is_none());
+    }
+
+    #[test]
+    fn unknown_group_is_an_error() {
+        let re = Regex::new(r"(?<a>\d+)").unwrap();
+        let err = SchemaExtractor::new(re, &[("b", FieldType::U64)]).unwrap_err();
+        assert_eq!(err.to_string(), r#"the regex has no group named "b""#);
+    }
+}
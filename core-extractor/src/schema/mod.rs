@@ -0,0 +1,5 @@
+mod extractor;
+mod record;
+
+pub use extractor::{FieldType, SchemaExtractor, UnknownGroup};
+pub use record::{FieldValue, Record};
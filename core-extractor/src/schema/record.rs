@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use analogz::containers::ArcStr;
+
+use crate::{datetime::DateTime, ip_addr::IpAddr, socket_addr::SocketAddr};
+
+/// A typed value of a [`Record`] field.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    Str(ArcStr),
+    U64(u64),
+    I64(i64),
+    F64(f64),
+    DateTime(DateTime),
+    IpAddr(IpAddr),
+    SocketAddr(SocketAddr),
+}
+
+/// The fields extracted from a text by a
+/// [`SchemaExtractor`](crate::schema::SchemaExtractor), in the order of its
+/// schema. A field is `None` when its group did not match or failed to parse.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    names: Arc<[String]>,
+    values: Vec<Option<FieldValue>>,
+}
+
+impl Record {
+    pub(crate) fn new(names: Arc<[String]>, values: Vec<Option<FieldValue>>) -> Self {
+        Self { names, values }
+    }
+
+    /// Returns the value of the field `name`, or `None` if it has no value or
+    /// is not part of the schema.
+    pub fn get(&self, name: &str) -> Option<&FieldValue> {
+        let idx = self.names.iter().position(|n| n == name)?;
+        self.values[idx].as_ref()
+    }
+
+    /// Returns the number of fields of the schema.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the fields as `(name, value)` pairs, in the order of the schema.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Option<&FieldValue>)> {
+        self.names
+            .iter()
+            .map(String::as_str)
+            .zip(self.values.iter().map(Option::as_ref))
+    }
+}
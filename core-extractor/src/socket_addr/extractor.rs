@@ -1,15 +1,30 @@
-use std::collections::HashSet;
+use analogz::containers::ArcStr;
 
-use crate::containers::{ArcStr, SocketAddr};
+use crate::{r#match::Match, socket_addr::SocketAddr};
+
+const DELIMITERS: &[char] = &[
+    ' ', '"', '$', '\'', '(', ')', ',', ';', '<', '>', '@', '[', ']', '`', '{', '|', '}', '=',
+];
 
 #[derive(Debug, Clone, Default)]
 pub struct SocketAddrExtractor {}
 
 impl SocketAddrExtractor {
     pub fn extract(&self, text: ArcStr) -> Option<SocketAddr> {
-        text.split(" \"$'(),;<>@[]`{|}=".chars().collect::<HashSet<char>>())
+        text.split(DELIMITERS)
             .find_map(|slice| slice.parse::<SocketAddr>().ok())
     }
+
+    /// Returns every address found in `text`, with its offsets relative to
+    /// `text`.
+    pub fn matches(&self, text: ArcStr) -> impl Iterator<Item = Match<SocketAddr>> {
+        let offset = text.start();
+        text.split(DELIMITERS).filter_map(move |slice| {
+            let value = slice.parse::<SocketAddr>().ok()?;
+            let start = slice.start() - offset;
+            Some(Match::new(start, start + slice.len(), value))
+        })
+    }
 }
 
 #[cfg(test)]
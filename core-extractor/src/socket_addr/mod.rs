@@ -0,0 +1,6 @@
+mod extractor;
+#[allow(clippy::module_inception)]
+mod socket_addr;
+
+pub use extractor::SocketAddrExtractor;
+pub use socket_addr::SocketAddr;
@@ -1,5 +1,11 @@
-use std::{ops::Deref, str::FromStr};
+use std::{fmt::Display, ops::Deref, str::FromStr};
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct SocketAddr(std::net::SocketAddr);
 
 impl SocketAddr {
@@ -16,6 +22,12 @@ impl FromStr for SocketAddr {
     }
 }
 
+impl Display for SocketAddr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
 impl Deref for SocketAddr {
     type Target = std::net::SocketAddr;
 
@@ -23,3 +35,18 @@ impl Deref for SocketAddr {
         &self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_matches_std() {
+        for s in ["192.168.1.1:8080", "[::1]:443", "[2001:db8::1]:53"] {
+            let addr = s.parse::<SocketAddr>().unwrap();
+            let std_addr = s.parse::<std::net::SocketAddr>().unwrap();
+            assert_eq!(format!("{addr}"), format!("{std_addr}"));
+            assert_eq!(addr.to_string(), s);
+        }
+    }
+}
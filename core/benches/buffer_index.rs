@@ -0,0 +1,45 @@
+//! Compares the sequential and the parallel build of the line index of a
+//! `Buffer` across content sizes, to pick
+//! `BufferBuilder::DEFAULT_PARALLEL_THRESHOLD`.
+//!
+//! Run with `cargo bench -p analogz --bench buffer_index`.
+
+use analogz::containers::Buffer;
+use criterion::{BatchSize, BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+fn log_content(bytes: usize) -> String {
+    let mut content = String::with_capacity(bytes + 64);
+    let mut id = 0;
+    while content.len() < bytes {
+        content.push_str(&format!(
+            "2024-05-01T12:00:{:02}Z INFO request {id} served in {}ms\n",
+            id % 60,
+            id % 97
+        ));
+        id += 1;
+    }
+    content.truncate(bytes);
+    content
+}
+
+fn index_build(c: &mut Criterion) {
+    let mut group = c.benchmark_group("buffer_index");
+    for kib in [4, 16, 32, 64, 128, 256, 1024, 4096] {
+        let content = log_content(kib * 1024);
+        group.throughput(Throughput::Bytes(content.len() as u64));
+        for (name, threshold) in [("sequential", usize::MAX), ("parallel", 0)] {
+            let builder = Buffer::builder().parallel_threshold(threshold);
+            group.bench_with_input(BenchmarkId::new(name, kib), &content, |b, content| {
+                b.iter_batched(
+                    || content.clone(),
+                    |content| builder.build(content),
+                    BatchSize::LargeInput,
+                )
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, index_build);
+criterion_main!(benches);
@@ -1,4 +1,7 @@
-use std::{ops::RangeBounds, sync::Arc};
+use std::{
+    ops::{Deref, RangeBounds},
+    sync::Arc,
+};
 
 #[derive(Debug, Clone)]
 pub struct ArcSlice<T> {
@@ -67,6 +70,27 @@ impl<T> ArcSlice<T> {
         ))
     }
 
+    pub fn first(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.get(self.len().checked_sub(1)?)
+    }
+
+    /// Returns the first item and an `ArcSlice` of the rest, sharing the same
+    /// backing storage, or `None` if the slice is empty.
+    pub fn split_first(&self) -> Option<(&T, ArcSlice<T>)> {
+        Some((self.first()?, self.slice(1..)))
+    }
+
+    /// Returns the last item and an `ArcSlice` of the rest, sharing the same
+    /// backing storage, or `None` if the slice is empty.
+    pub fn split_last(&self) -> Option<(&T, ArcSlice<T>)> {
+        let last = self.len().checked_sub(1)?;
+        Some((self.get(last)?, self.slice(..last)))
+    }
+
     pub fn len(&self) -> usize {
         self.end - self.start
     }
@@ -78,6 +102,62 @@ impl<T> ArcSlice<T> {
     pub fn as_slice(&self) -> &[T] {
         &self.slice[self.start..self.end]
     }
+
+    /// Returns the items within the slice window mutably, or `None` if the
+    /// items are shared with another `ArcSlice`, as in `Arc::get_mut`.
+    pub fn get_mut(&mut self) -> Option<&mut [T]> {
+        Arc::get_mut(&mut self.slice).map(|slice| &mut slice[self.start..self.end])
+    }
+
+    /// Returns an iterator over clones of the items within the slice window.
+    pub fn iter_cloned(&self) -> impl Iterator<Item = T> + '_
+    where
+        T: Clone,
+    {
+        self.as_slice().iter().cloned()
+    }
+}
+
+/// Owning iterator over an `ArcSlice`, yielding clones of its items.
+///
+/// Created by `ArcSlice::into_iter`.
+#[derive(Debug, Clone)]
+pub struct IntoIter<T> {
+    slice: ArcSlice<T>,
+}
+
+impl<T: Clone> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.slice.get(0).cloned()?;
+        self.slice.start += 1;
+        Some(next)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let l = self.slice.len();
+        (l, Some(l))
+    }
+}
+
+impl<T: Clone> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let next = self.slice.get(self.slice.len().checked_sub(1)?).cloned()?;
+        self.slice.end -= 1;
+        Some(next)
+    }
+}
+
+impl<T: Clone> ExactSizeIterator for IntoIter<T> {}
+
+impl<T: Clone> IntoIterator for ArcSlice<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { slice: self }
+    }
 }
 
 impl<T, C> From<C> for ArcSlice<T>
@@ -89,6 +169,14 @@ where
     }
 }
 
+impl<T> Deref for ArcSlice<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
 impl<T> AsRef<[T]> for ArcSlice<T> {
     fn as_ref(&self) -> &[T] {
         self.as_slice()
@@ -233,6 +321,72 @@ mod tests {
         assert!(Arc::ptr_eq(&slice1.slice, &slice3.slice));
     }
 
+    #[test]
+    fn test_iter_cloned_respects_window() {
+        let slice = ArcSlice::new(vec!["a".to_string(), "b".into(), "c".into(), "d".into()]);
+        let window = slice.slice(1..3);
+        assert_eq!(window.iter_cloned().collect::<Vec<_>>(), vec!["b", "c"]);
+    }
+
+    #[test]
+    fn test_into_iter_respects_window() {
+        let slice = ArcSlice::new(vec![1, 2, 3, 4, 5]).slice(1..4);
+        let iter = slice.clone().into_iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(slice.into_iter().rev().collect::<Vec<_>>(), vec![4, 3, 2]);
+    }
+
+    #[test]
+    fn test_into_iter_for_loop() {
+        let mut sum = 0;
+        for item in ArcSlice::new(vec![1, 2, 3]) {
+            sum += item;
+        }
+        assert_eq!(sum, 6);
+    }
+
+    #[test]
+    fn test_first_and_last_respect_window() {
+        let slice = ArcSlice::new(vec![1, 2, 3, 4, 5]);
+        let window = slice.slice(1..4);
+        assert_eq!(window.first(), Some(&2));
+        assert_eq!(window.last(), Some(&4));
+        assert_eq!(slice.slice(2..2).first(), None);
+        assert_eq!(slice.slice(2..2).last(), None);
+    }
+
+    #[test]
+    fn test_split_first_and_last() {
+        let window = ArcSlice::new(vec![1, 2, 3, 4, 5]).slice(1..4);
+
+        let (first, rest) = window.split_first().unwrap();
+        assert_eq!(*first, 2);
+        assert_eq!(rest.as_slice(), &[3, 4]);
+
+        let (last, rest) = window.split_last().unwrap();
+        assert_eq!(*last, 4);
+        assert_eq!(rest.as_slice(), &[2, 3]);
+
+        let single = window.slice(0..1);
+        let (first, rest) = single.split_first().unwrap();
+        assert_eq!((*first, rest.is_empty()), (2, true));
+        assert!(rest.split_first().is_none());
+        assert!(rest.split_last().is_none());
+    }
+
+    #[test]
+    fn test_get_mut_requires_unique_ownership() {
+        let mut slice = ArcSlice::new(vec![1, 2, 3, 4]).slice(1..3);
+        slice.get_mut().unwrap()[0] = 20;
+        assert_eq!(slice.as_slice(), [20, 3]);
+
+        let shared = slice.clone();
+        assert!(slice.get_mut().is_none());
+        drop(shared);
+        assert_eq!(slice.get_mut().unwrap(), [20, 3]);
+    }
+
     #[test]
     fn test_clone() {
         let data = vec![1, 2, 3, 4, 5];
@@ -1,16 +1,21 @@
 use crate::{
-    containers::pattern::{Pattern, Searcher},
+    containers::pattern::{Pattern, ReverseSearcher, Searcher},
     misc::{
         chars::{CharIndices, Chars},
-        split::Split,
+        fields::Fields,
+        split::{RSplit, Split},
+        tokens::{Token, TokenCounts, TokenKind, Tokens},
         window::Windows,
     },
 };
+use itertools::Itertools;
 use std::{
     borrow::Borrow,
     fmt::{Debug, Display},
-    hash::Hash,
-    ops::{Deref, RangeBounds},
+    hash::{Hash, Hasher},
+    iter::Rev,
+    ops::{Deref, Range, RangeBounds},
+    str::FromStr,
     sync::Arc,
 };
 
@@ -37,6 +42,29 @@ impl ArcStr {
         &self.astr[self.start..self.end]
     }
 
+    /// Returns the `Arc<str>` backing this slice, for APIs that take an
+    /// `Arc<str>` without copying.
+    ///
+    /// This is the *entire* backing string, not just the window between
+    /// `start()` and `end()`; use [`ArcStr::as_str`] for the windowed content.
+    ///
+    /// ```
+    /// use analogz::containers::ArcStr;
+    ///
+    /// let line = ArcStr::from("GET /index 200").slice(4..10);
+    /// assert_eq!(line.as_str(), "/index");
+    /// assert_eq!(&**line.as_arc_str(), "GET /index 200");
+    /// ```
+    pub fn as_arc_str(&self) -> &Arc<str> {
+        &self.astr
+    }
+
+    /// Converts into the `Arc<str>` backing this slice. Like
+    /// [`ArcStr::as_arc_str`], this is the entire backing string, not the window.
+    pub fn into_arc_str(self) -> Arc<str> {
+        self.astr
+    }
+
     pub fn slice(&self, rng: impl RangeBounds<usize>) -> Self {
         let start = match rng.start_bound() {
             std::ops::Bound::Included(i) => self.start + i,
@@ -81,6 +109,18 @@ impl ArcStr {
         pat.into_searcher(self.clone()).next_match()
     }
 
+    /// Returns the absolute byte offset of the first occurrence of `pat`, in
+    /// the same coordinates as [`ArcStr::start`].
+    ///
+    /// Unlike `find`, whose offsets are relative to the slice, this does not go
+    /// through a searcher. Like `find`, an empty `pat` never matches.
+    pub fn find_index(&self, pat: &str) -> Option<usize> {
+        if pat.is_empty() {
+            return None;
+        }
+        self.as_str().find(pat).map(|idx| self.start + idx)
+    }
+
     pub fn find_iter<P: Pattern>(&self, pat: P) -> P::Searcher {
         pat.into_searcher(self.clone())
     }
@@ -89,18 +129,133 @@ impl ArcStr {
         Split::new(self.clone(), pat)
     }
 
+    /// Like [`ArcStr::split`], but a trailing delimiter does not produce an
+    /// empty last field, as in `str::split_terminator`.
+    ///
+    /// ```
+    /// use analogz::containers::ArcStr;
+    ///
+    /// let row = ArcStr::from("a,b,");
+    /// assert_eq!(row.split(",").collect::<Vec<_>>(), ["a", "b", ""]);
+    /// assert_eq!(row.split_terminator(",").collect::<Vec<_>>(), ["a", "b"]);
+    /// ```
+    pub fn split_terminator<P: Pattern>(&self, pat: P) -> Split<P::Searcher> {
+        Split::terminator(self.clone(), pat)
+    }
+
+    /// Like [`ArcStr::split`], but yields the fields from the last one to the
+    /// first, so a trailing delimiter produces a leading empty field.
+    ///
+    /// ```
+    /// use analogz::containers::ArcStr;
+    ///
+    /// let path = ArcStr::from("a.b.c");
+    /// assert_eq!(path.rsplit(".").collect::<Vec<_>>(), ["c", "b", "a"]);
+    /// ```
+    pub fn rsplit<P>(&self, pat: P) -> RSplit<P::Searcher>
+    where
+        P: Pattern,
+        P::Searcher: ReverseSearcher,
+    {
+        RSplit::new(self.clone(), pat)
+    }
+
+    /// Returns the tokens of the slice, between a `Token::Start` and a
+    /// `Token::End`. See [`Token`](crate::misc::tokens::Token) for how the
+    /// slice is split.
+    pub fn tokenize(&self) -> Tokens {
+        Tokens::new(self.clone())
+    }
+
+    /// Counts the tokens of each kind of the slice in one pass over
+    /// [`ArcStr::tokenize`].
+    pub fn token_class_counts(&self) -> TokenCounts {
+        self.tokenize().collect()
+    }
+
+    /// Returns the tokens of the slice that carry content: words and symbols,
+    /// without the `Start`/`End` sentinels and whitespace of
+    /// [`ArcStr::tokenize`].
+    ///
+    /// ```
+    /// use analogz::containers::ArcStr;
+    ///
+    /// let words = ArcStr::from("user=bob  id=7")
+    ///     .content_tokens()
+    ///     .filter_map(|t| t.as_arc_str().cloned())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(words, ["user", "=", "bob", "id", "=", "7"]);
+    /// ```
+    pub fn content_tokens(&self) -> impl Iterator<Item = Token> + use<> {
+        self.tokenize().filter(|t| {
+            !matches!(
+                t.kind(),
+                TokenKind::Start | TokenKind::End | TokenKind::Whitespace
+            )
+        })
+    }
+
+    /// Returns an iterator over the fields separated by `delim`, each paired with
+    /// its 0-based index. When `quote` is given, delimiters between a pair of
+    /// quote characters do not split the field (the quotes are kept).
+    pub fn fields(&self, delim: char, quote: Option<char>) -> Fields {
+        Fields::new(self.clone(), delim, quote)
+    }
+
+    /// Returns the number of non-overlapping occurrences of `pat`, without
+    /// building a slice per match. An empty `pat` counts as `0`.
+    pub fn matches_count(&self, pat: &str) -> usize {
+        if pat.is_empty() {
+            return 0;
+        }
+        self.as_str().matches(pat).count()
+    }
+
     pub fn contains<P: Pattern>(&self, pat: P) -> bool {
         self.find(pat).is_some()
     }
 
+    /// Returns the length of the slice in bytes, like `str::len`.
+    ///
+    /// Prefer [`ArcStr::byte_len`] or [`ArcStr::char_len`] where the unit
+    /// matters to the reader.
     pub fn len(&self) -> usize {
         self.end.saturating_sub(self.start)
     }
 
+    /// Returns the length of the slice in bytes.
+    pub fn byte_len(&self) -> usize {
+        self.len()
+    }
+
+    /// Returns the length of the slice in chars; an alias of
+    /// [`ArcStr::char_count`].
+    pub fn char_len(&self) -> usize {
+        self.char_count()
+    }
+
+    /// Checks if all chars of the slice are ASCII, in which case byte and char
+    /// offsets coincide.
+    pub fn is_ascii(&self) -> bool {
+        self.as_str().is_ascii()
+    }
+
     pub fn is_empty(&self) -> bool {
         self.start == self.end
     }
 
+    /// Returns the number of chars in the slice.
+    ///
+    /// This is O(n): ASCII slices are detected with `str::is_ascii` and counted
+    /// by their byte length, other slices are walked char by char.
+    pub fn char_count(&self) -> usize {
+        if self.is_ascii() {
+            self.len()
+        } else {
+            self.as_str().chars().count()
+        }
+    }
+
     pub fn chars(&self) -> Chars {
         self.clone().into()
     }
@@ -109,10 +264,224 @@ impl ArcStr {
         self.clone().into()
     }
 
+    /// Returns an iterator over the chars of the slice, from the last one to
+    /// the first.
+    ///
+    /// ```
+    /// use analogz::containers::ArcStr;
+    ///
+    /// let id = ArcStr::from("req-0042");
+    /// let digits = id.chars_rev().take_while(char::is_ascii_digit).count();
+    /// assert_eq!(id.slice(id.len() - digits..), "0042");
+    /// ```
+    pub fn chars_rev(&self) -> Rev<Chars> {
+        self.chars().rev()
+    }
+
+    /// Returns an iterator over the overlapping sub-slices of `size` chars,
+    /// advancing one char at a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// ```
+    /// use analogz::containers::ArcStr;
+    ///
+    /// let windows = ArcStr::from("abcd").windows(3).collect::<Vec<_>>();
+    /// assert_eq!(windows, ["abc", "bcd"]);
+    /// ```
     pub fn windows(&self, size: usize) -> Windows {
         Windows::new(self.clone(), size)
     }
 
+    /// Returns an iterator over the extended grapheme clusters of the slice,
+    /// each as a sub-slice sharing the same offsets.
+    #[cfg(feature = "unicode")]
+    pub fn graphemes(&self) -> impl Iterator<Item = ArcStr> + '_ {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        self.as_str()
+            .grapheme_indices(true)
+            .map(|(idx, g)| self.slice(idx..idx + g.len()))
+    }
+
+    /// Returns the overlapping sub-slices of `size` grapheme clusters, like
+    /// [`ArcStr::windows`] but never cutting a cluster in two.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    #[cfg(feature = "unicode")]
+    pub fn grapheme_windows(&self, size: usize) -> impl Iterator<Item = ArcStr> + '_ {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        assert!(size > 0, "window size must be greater than zero");
+        let borders = self
+            .as_str()
+            .grapheme_indices(true)
+            .map(|(idx, _)| idx)
+            .chain([self.len()])
+            .collect_vec();
+        (0..borders.len().saturating_sub(size))
+            .map(move |i| self.slice(borders[i]..borders[i + size]))
+    }
+
+    /// Returns a new `ArcStr` where leading and trailing whitespace is removed
+    /// and every internal run of whitespace is collapsed into a single space.
+    ///
+    /// When the slice is already normalized it is returned as a cheap clone
+    /// sharing the backing string. Otherwise the result is backed by a freshly
+    /// allocated string, so it does not share offsets with `self`.
+    pub fn normalize_whitespace(&self) -> ArcStr {
+        let mut prev_ws = false;
+        let is_normalized = self.as_str().char_indices().all(|(idx, c)| {
+            let ws = c.is_whitespace();
+            let ok = !ws || (c == ' ' && idx > 0 && !prev_ws);
+            prev_ws = ws;
+            ok
+        }) && !prev_ws;
+        if is_normalized {
+            self.clone()
+        } else {
+            ArcStr::from(self.as_str().split_whitespace().join(" "))
+        }
+    }
+
+    /// Returns the slice with ASCII letters converted to lowercase.
+    ///
+    /// When there is no ASCII uppercase letter, the slice is returned as a
+    /// cheap clone sharing the backing string instead of being copied.
+    ///
+    /// ```
+    /// use analogz::containers::ArcStr;
+    ///
+    /// assert_eq!(ArcStr::from("WARN Disk").to_ascii_lowercase(), "warn disk");
+    /// ```
+    pub fn to_ascii_lowercase(&self) -> ArcStr {
+        if self.as_str().bytes().any(|b| b.is_ascii_uppercase()) {
+            ArcStr::from(self.as_str().to_ascii_lowercase())
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Returns the slice converted to lowercase with the full Unicode rules
+    /// of `str::to_lowercase`.
+    ///
+    /// The result is backed by a freshly allocated string, so it starts at
+    /// offset `0`. The conversion does not depend on a locale: the Turkish
+    /// dotted `İ`, for instance, becomes `i̇` rather than `i`.
+    ///
+    /// ```
+    /// use analogz::containers::ArcStr;
+    ///
+    /// assert_eq!(ArcStr::from("ÉCHEC Größe").to_lowercase(), "échec größe");
+    /// ```
+    pub fn to_lowercase(&self) -> ArcStr {
+        ArcStr::from(self.as_str().to_lowercase())
+    }
+
+    /// Returns the slice converted to uppercase with the full Unicode rules
+    /// of `str::to_uppercase`, e.g. `ß` becomes `SS`. See
+    /// [`ArcStr::to_lowercase`] for the caveats.
+    pub fn to_uppercase(&self) -> ArcStr {
+        ArcStr::from(self.as_str().to_uppercase())
+    }
+
+    /// Returns the sub-slice with every leading and trailing `c` removed.
+    pub fn trim_matches(&self, c: char) -> ArcStr {
+        let s = self.as_str();
+        let start = s.len() - s.trim_start_matches(c).len();
+        let end = s.trim_end_matches(c).len().max(start);
+        self.slice(start..end)
+    }
+
+    /// Returns the sub-slice covering the chars in `rng`, where `rng` counts
+    /// chars rather than bytes, or `None` if `rng` is decreasing or goes past
+    /// the last char.
+    ///
+    /// ```
+    /// use analogz::containers::ArcStr;
+    ///
+    /// let astr = ArcStr::from("né à Zürich");
+    /// assert_eq!(astr.slice_chars(5..11).unwrap(), "Zürich");
+    /// assert_eq!(astr.slice_chars(5..12), None);
+    /// ```
+    pub fn slice_chars(&self, rng: Range<usize>) -> Option<ArcStr> {
+        let len = rng.end.checked_sub(rng.start)?;
+        let s = self.as_str();
+        let mut offsets = s.char_indices().map(|(idx, _)| idx).chain([s.len()]);
+        let start = offsets.nth(rng.start)?;
+        let end = match len.checked_sub(1) {
+            Some(n) => offsets.nth(n)?,
+            None => start,
+        };
+        Some(self.slice(start..end))
+    }
+
+    /// Parses the slice into a `T`, like `str::parse`.
+    pub fn parse<T: FromStr>(&self) -> Result<T, T::Err> {
+        self.as_str().parse()
+    }
+
+    /// Like [`ArcStr::parse`], but the error carries the offsets of the slice
+    /// in its backing string, to report where parsing failed.
+    ///
+    /// ```
+    /// use analogz::containers::ArcStr;
+    ///
+    /// let line = ArcStr::from("status=OK bytes=12k");
+    /// let err = line.slice(16..).parse_located::<u64>().unwrap_err();
+    /// assert_eq!(err.range, 16..19);
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "failed to parse at bytes 16..19: invalid digit found in string"
+    /// );
+    /// ```
+    pub fn parse_located<T: FromStr>(&self) -> Result<T, LocatedParseError<T::Err>> {
+        self.parse().map_err(|error| LocatedParseError {
+            range: self.start..self.end,
+            error,
+        })
+    }
+
+    /// Returns the sub-slice without leading and trailing whitespace.
+    pub fn trim(&self) -> ArcStr {
+        let s = self.as_str();
+        let start = s.len() - s.trim_start().len();
+        let end = s.trim_end().len().max(start);
+        self.slice(start..end)
+    }
+
+    /// Returns `true` if both slices are equal once leading and trailing
+    /// whitespace is ignored. Nothing is allocated.
+    pub fn eq_trimmed(&self, other: &ArcStr) -> bool {
+        self.as_str().trim() == other.as_str().trim()
+    }
+
+    /// Feeds the slice without leading and trailing whitespace into `state`,
+    /// so slices equal under [`ArcStr::eq_trimmed`] hash the same.
+    pub fn hash_trimmed<H: Hasher>(&self, state: &mut H) {
+        self.as_str().trim().hash(state);
+    }
+
+    /// Returns the sub-slice after `prefix`, or `None` if the slice does not
+    /// start with it.
+    pub fn strip_prefix(&self, prefix: &str) -> Option<ArcStr> {
+        self.as_str()
+            .starts_with(prefix)
+            .then(|| self.slice(prefix.len()..))
+    }
+
+    /// Returns the sub-slice before `suffix`, or `None` if the slice does not
+    /// end with it.
+    pub fn strip_suffix(&self, suffix: &str) -> Option<ArcStr> {
+        self.as_str()
+            .ends_with(suffix)
+            .then(|| self.slice(..self.len() - suffix.len()))
+    }
+
     /// Returns the relative position (as an `isize`) of another `ArcStr`'s start
     /// index with respect to this `ArcStr`'s start index, if both slices refer to
     /// the same underlying `Arc<str>`. If they do not, returns `None`.
@@ -123,19 +492,48 @@ impl ArcStr {
     /// # Example
     /// ```
     /// use analogz::containers::ArcStr;
-    /// let base = ArcStr::new("hello world");
+    /// let base = ArcStr::from("hello world");
     /// let left = base.slice(0..5); // "hello"
     /// let right = base.slice(6..); // "world"
     /// assert_eq!(left.relative_position(&right), Some(6));
     /// assert_eq!(right.relative_position(&left), Some(-6));
     /// assert_eq!(left.relative_position(&left), Some(0));
     ///
-    /// let unrelated = ArcStr::new("other");
+    /// let unrelated = ArcStr::from("other");
     /// assert_eq!(left.relative_position(&unrelated), None);
     /// ```
     pub fn relative_position(&self, other: &ArcStr) -> Option<isize> {
         Arc::ptr_eq(&self.astr, &other.astr).then_some(other.start as isize - self.start as isize)
     }
+
+    /// Returns whether `self` starts before `other`, or `None` if they are not
+    /// slices of the same string.
+    pub fn precedes(&self, other: &ArcStr) -> Option<bool> {
+        self.relative_position(other).map(|pos| pos > 0)
+    }
+
+    /// Compares two slices of the same string by their start offset, then by
+    /// their end offset, for use with `sort_by`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices do not share the same backing string.
+    ///
+    /// ```
+    /// use analogz::containers::ArcStr;
+    ///
+    /// let line = ArcStr::from("user=bob ip=10.0.0.1 took=12ms");
+    /// let mut fields = vec![line.slice(21..), line.slice(0..8), line.slice(9..20)];
+    /// fields.sort_by(ArcStr::by_position);
+    /// assert_eq!(fields, ["user=bob", "ip=10.0.0.1", "took=12ms"]);
+    /// ```
+    pub fn by_position(a: &ArcStr, b: &ArcStr) -> std::cmp::Ordering {
+        assert!(
+            Arc::ptr_eq(&a.astr, &b.astr),
+            "slices of different strings have no relative position"
+        );
+        (a.start, a.end).cmp(&(b.start, b.end))
+    }
 }
 
 impl Debug for ArcStr {
@@ -144,6 +542,15 @@ impl Debug for ArcStr {
     }
 }
 
+/// Error of [`ArcStr::parse_located`]: the error of `T::from_str` along with
+/// the offsets of the slice that failed to parse.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("failed to parse at bytes {}..{}: {error}", range.start, range.end)]
+pub struct LocatedParseError<E> {
+    pub range: Range<usize>,
+    pub error: E,
+}
+
 impl Display for ArcStr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         Display::fmt(self.as_ref(), f)
@@ -226,6 +633,90 @@ impl Hash for ArcStr {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_slice_chars_converts_char_indices_to_bytes() {
+        let base = ArcStr::from("~~日本語 ok ✓");
+        let astr = base.slice(2..);
+        assert_eq!(astr.char_len(), 8);
+        let kanji = astr.slice_chars(1..3).unwrap();
+        assert_eq!(kanji, "本語");
+        assert_eq!((kanji.start(), kanji.end()), (5, 11));
+        assert_eq!(astr.slice_chars(7..8).unwrap(), "✓");
+        assert_eq!(astr.slice_chars(0..8).unwrap(), astr);
+        assert_eq!(astr.slice_chars(8..8).unwrap(), "");
+        assert_eq!(astr.slice_chars(3..3).unwrap().start(), 11);
+        assert_eq!(astr.slice_chars(0..9), None);
+        assert_eq!(astr.slice_chars(9..9), None);
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = astr.slice_chars(3..1);
+        assert_eq!(reversed, None);
+    }
+
+    #[test]
+    fn test_parse_and_parse_located() {
+        let line = ArcStr::from("took 250 ms, retried 3x");
+        assert_eq!(line.slice(5..8).parse::<u32>(), Ok(250));
+        assert_eq!(line.slice(5..8).parse_located::<u32>(), Ok(250));
+
+        let err = line.slice(21..23).parse_located::<u32>().unwrap_err();
+        assert_eq!(err.range, 21..23);
+        assert_eq!(err.error, "3x".parse::<u32>().unwrap_err());
+        assert!(
+            err.to_string()
+                .starts_with("failed to parse at bytes 21..23: ")
+        );
+    }
+
+    #[test]
+    fn test_rsplit_yields_fields_from_the_end() {
+        let base = ArcStr::from("xx|a.b.c");
+        let path = base.slice(3..);
+        let fields = path.rsplit(".").collect::<Vec<_>>();
+        assert_eq!(fields, ["c", "b", "a"]);
+        assert_eq!(
+            fields.iter().map(|f| f.start()).collect::<Vec<_>>(),
+            [7, 5, 3]
+        );
+
+        let trailing = ArcStr::from("a.b.");
+        assert_eq!(trailing.rsplit(".").collect::<Vec<_>>(), ["", "b", "a"]);
+        assert_eq!(ArcStr::from("").rsplit(".").collect::<Vec<_>>(), [""]);
+
+        for input in ["a,b;c", ",a,,b,", "é;ü,ö", "", "abc"] {
+            let expected = input.rsplit([',', ';']).collect::<Vec<_>>();
+            let astr = ArcStr::from(input);
+            assert_eq!(astr.rsplit(&[',', ';'][..]).collect::<Vec<_>>(), expected);
+            let normalized = ArcStr::from(input.replace(';', ","));
+            assert_eq!(normalized.rsplit(',').collect::<Vec<_>>(), expected);
+            assert_eq!(normalized.rsplit(",").collect::<Vec<_>>(), expected);
+        }
+        assert_eq!(
+            ArcStr::from("aaa").rsplit("aa").collect::<Vec<_>>(),
+            ["", "a"]
+        );
+    }
+
+    #[test]
+    fn test_eq_trimmed_ignores_surrounding_whitespace() {
+        use std::hash::{BuildHasher, RandomState};
+
+        let padded = ArcStr::from("  foo \t");
+        let plain = ArcStr::from("foo");
+        assert!(padded.eq_trimmed(&plain));
+        assert!(!padded.eq_trimmed(&ArcStr::from("f oo")));
+        assert_eq!(padded.trim(), "foo");
+        assert_eq!(padded.trim().start(), 2);
+        assert_eq!(ArcStr::from("   ").trim(), "");
+
+        let hasher = RandomState::new();
+        let hash = |astr: &ArcStr| {
+            let mut state = hasher.build_hasher();
+            astr.hash_trimmed(&mut state);
+            state.finish()
+        };
+        assert_eq!(hash(&padded), hash(&plain));
+    }
+
     #[test]
     fn test_new_creates_from_string() {
         let s = "hello world";
@@ -393,6 +884,198 @@ mod tests {
         assert!(debug_str.contains("hello"));
     }
 
+    #[test]
+    fn test_normalize_whitespace() {
+        let arc_str = ArcStr::from("  a   b\t c ");
+        assert_eq!(arc_str.normalize_whitespace(), "a b c");
+    }
+
+    #[test]
+    fn test_normalize_whitespace_keeps_non_ascii() {
+        let arc_str = ArcStr::from("x  héllo\n\n  wörld ");
+        let normalized = arc_str.slice(1..).normalize_whitespace();
+        assert_eq!(normalized, "héllo wörld");
+        assert_eq!(normalized.start(), 0);
+        assert_eq!(normalized.end(), normalized.len());
+    }
+
+    #[test]
+    fn test_normalize_whitespace_reuses_normalized_backing() {
+        let arc_str = ArcStr::from("[a b c]");
+        let inner = arc_str.slice(1..6);
+        let normalized = inner.normalize_whitespace();
+        assert!(Arc::ptr_eq(normalized.as_arc_str(), arc_str.as_arc_str()));
+        assert_eq!((normalized.start(), normalized.end()), (1, 6));
+
+        for s in [" a", "a ", "a  b", "a\tb", "\u{a0}"] {
+            let arc_str = ArcStr::from(s);
+            let normalized = arc_str.normalize_whitespace();
+            assert!(!Arc::ptr_eq(normalized.as_arc_str(), arc_str.as_arc_str()));
+            assert_eq!(normalized, s.split_whitespace().join(" "));
+        }
+        assert_eq!(ArcStr::from("").normalize_whitespace(), "");
+    }
+
+    #[test]
+    fn test_to_ascii_lowercase_shares_backing_when_unchanged() {
+        let arc_str = ArcStr::from("ERROR: disk café full");
+        let lower = arc_str.slice(7..).to_ascii_lowercase();
+        assert!(Arc::ptr_eq(lower.as_arc_str(), arc_str.as_arc_str()));
+        assert_eq!(lower, "disk café full");
+
+        let lower = arc_str.to_ascii_lowercase();
+        assert!(!Arc::ptr_eq(lower.as_arc_str(), arc_str.as_arc_str()));
+        assert_eq!(lower, "error: disk café full");
+    }
+
+    #[test]
+    fn test_content_tokens_skip_sentinels_and_whitespace() {
+        let tokens = ArcStr::from(" a  b ").content_tokens().collect::<Vec<_>>();
+        assert_eq!(
+            tokens,
+            [
+                Token::Alphabetic(ArcStr::from(" a  b ").slice(1..2)),
+                Token::Alphabetic(ArcStr::from(" a  b ").slice(4..5)),
+            ]
+        );
+        assert_eq!(ArcStr::from("   ").content_tokens().count(), 0);
+        assert_eq!(ArcStr::from("").content_tokens().count(), 0);
+    }
+
+    #[test]
+    fn test_unicode_case_conversion() {
+        let arc_str = ArcStr::from("[ÀÉÎ Straße]");
+        let lower = arc_str.slice(1..arc_str.len() - 1).to_lowercase();
+        assert_eq!(lower, "àéî straße");
+        assert_eq!((lower.start(), lower.end()), (0, lower.as_arc_str().len()));
+
+        let upper = arc_str.slice(1..arc_str.len() - 1).to_uppercase();
+        assert_eq!(upper, "ÀÉÎ STRASSE");
+        assert_eq!((upper.start(), upper.end()), (0, upper.as_arc_str().len()));
+    }
+
+    #[test]
+    fn test_char_count() {
+        let ascii = ArcStr::from("-- hello world --").slice(3..14);
+        assert_eq!(ascii.char_count(), ascii.as_str().chars().count());
+        assert_eq!(ascii.char_count(), 11);
+
+        let unicode = ArcStr::from("héllo wörld 日本");
+        assert_eq!(unicode.char_count(), unicode.as_str().chars().count());
+        assert_eq!(unicode.char_count(), 14);
+        assert_eq!(ArcStr::from("").char_count(), 0);
+    }
+
+    #[test]
+    fn test_is_ascii_and_lengths() {
+        let mixed = ArcStr::from("GET /café 200");
+        let ascii = mixed.slice(..8);
+        assert!(ascii.is_ascii());
+        assert_eq!((ascii.byte_len(), ascii.char_len()), (8, 8));
+        assert!(!mixed.is_ascii());
+        assert_eq!((mixed.byte_len(), mixed.char_len()), (14, 13));
+        assert!(ArcStr::from("").is_ascii());
+
+        for s in ["", "abc", "héllo", "日本語", "a\u{1F600}b"] {
+            let arc_str = ArcStr::from(s);
+            assert!(arc_str.char_len() <= arc_str.byte_len());
+            assert_eq!(arc_str.byte_len(), arc_str.len());
+        }
+    }
+
+    #[test]
+    fn test_into_arc_str_shares_backing() {
+        let source = ArcStr::from("2024-01-15 INFO started");
+        let window = source.slice(11..15);
+        assert!(Arc::ptr_eq(window.as_arc_str(), source.as_arc_str()));
+
+        let backing = window.into_arc_str();
+        assert!(Arc::ptr_eq(&backing, source.as_arc_str()));
+        assert_eq!(&*backing, "2024-01-15 INFO started");
+    }
+
+    #[test]
+    fn test_trim_matches_keeps_offsets() {
+        let arc_str = ArcStr::from("key=\"value\"");
+        let quoted = arc_str.slice(4..);
+        let value = quoted.trim_matches('"');
+        assert_eq!(value, "value");
+        assert_eq!((value.start(), value.end()), (5, 10));
+
+        assert_eq!(ArcStr::from("\"\"\"").trim_matches('"'), "");
+        assert_eq!(ArcStr::from("plain").trim_matches('"'), "plain");
+    }
+
+    #[test]
+    fn test_strip_prefix_and_suffix() {
+        let arc_str = ArcStr::from("[\"value\"]").slice(1..8);
+        let stripped = arc_str
+            .strip_prefix("\"")
+            .and_then(|s| s.strip_suffix("\""))
+            .unwrap();
+        assert_eq!(stripped, "value");
+        assert_eq!((stripped.start(), stripped.end()), (2, 7));
+
+        assert_eq!(arc_str.strip_prefix("value"), None);
+        assert_eq!(arc_str.strip_suffix("value"), None);
+        assert_eq!(arc_str.strip_prefix(""), Some(arc_str.clone()));
+    }
+
+    #[test]
+    fn test_find_index_is_absolute() {
+        let arc_str = ArcStr::from("id=1 id=2 id=3").slice(5..);
+        let (rel, _) = arc_str.find("id=").unwrap();
+        assert_eq!(arc_str.find_index("id="), Some(arc_str.start() + rel));
+        assert_eq!(arc_str.find_index("id="), Some(5));
+        assert_eq!(arc_str.find_index("=3"), Some(12));
+        assert_eq!(arc_str.find_index("id=1"), None);
+        assert_eq!(arc_str.find_index(""), None);
+    }
+
+    #[test]
+    fn test_matches_count() {
+        assert_eq!(ArcStr::from("aaaa").matches_count("aa"), 2);
+        assert_eq!(ArcStr::from("aaaaa").matches_count("aa"), 2);
+        let line = ArcStr::from("retry, retry, ok, retry").slice(7..);
+        assert_eq!(line.matches_count("retry"), 2);
+        assert_eq!(line.matches_count(","), 2);
+        assert_eq!(line.matches_count("missing"), 0);
+        assert_eq!(line.matches_count(""), 0);
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_graphemes_keep_combining_marks() {
+        let arc_str = ArcStr::from("> cafe\u{301} 🇫🇷!").slice(2..);
+        let graphemes = arc_str.graphemes().collect_vec();
+        assert_eq!(graphemes, ["c", "a", "f", "e\u{301}", " ", "🇫🇷", "!"]);
+        assert_eq!((graphemes[3].start(), graphemes[3].end()), (5, 8));
+
+        let windows = arc_str.slice(2..).grapheme_windows(2).collect_vec();
+        assert_eq!(windows, ["fe\u{301}", "e\u{301} ", " 🇫🇷", "🇫🇷!"]);
+        assert_eq!(arc_str.grapheme_windows(8).count(), 0);
+    }
+
+    #[test]
+    fn test_ordering_slices_by_position() {
+        let line = ArcStr::from("a=1 b=2 c=3");
+        let (a, b, c) = (line.slice(0..3), line.slice(4..7), line.slice(8..));
+        assert_eq!(a.precedes(&b), Some(true));
+        assert_eq!(c.precedes(&b), Some(false));
+        assert_eq!(a.precedes(&a), Some(false));
+        assert_eq!(a.precedes(&ArcStr::from("a=1")), None);
+
+        let mut fields = vec![c.clone(), a.clone(), b.clone()];
+        fields.sort_by(ArcStr::by_position);
+        assert_eq!(fields, [a, b, c]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_by_position_panics_for_unrelated_slices() {
+        ArcStr::by_position(&ArcStr::from("a"), &ArcStr::from("a"));
+    }
+
     #[test]
     fn test_hash() {
         use std::collections::HashMap;
@@ -401,4 +1084,16 @@ mod tests {
         map.insert(arc_str.clone(), 42);
         assert_eq!(map.get(&arc_str), Some(&42));
     }
+
+    #[test]
+    fn test_hash_of_sliced_equals_owned() {
+        use std::collections::HashSet;
+        let sliced = ArcStr::from("level=error msg=\"value\"").slice(6..11);
+        let owned = ArcStr::from("error");
+        assert_eq!(sliced, owned);
+
+        let set: HashSet<ArcStr> = [sliced, owned].into_iter().collect();
+        assert_eq!(set.len(), 1);
+        assert!(set.contains(&ArcStr::from("error")));
+    }
 }
@@ -1,13 +1,41 @@
-use std::ops::{Deref, Range};
+#[cfg(feature = "flate2")]
+use std::io::Read;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    hash::{BuildHasher, BuildHasherDefault, DefaultHasher, Hasher},
+    io,
+    ops::{Bound, Deref, Range, RangeBounds},
+    path::Path,
+};
+#[cfg(not(feature = "no-threads"))]
+use std::{
+    panic,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
 use itertools::Itertools;
+#[cfg(feature = "polars")]
+use polars::prelude::{DataFrame, IntoColumn, NewChunkedArray, StringChunked};
+use regex::Regex;
 
+#[cfg(not(feature = "no-threads"))]
+use crate::misc::stepped_range::SteppedRange;
 use crate::{
-    containers::{ArcSlice, InvalidIndexError},
-    misc::stepped_range::SteppedRange,
+    containers::{ArcSlice, CaseInsensitive, FromLine, InvalidIndexError},
+    indices::CutIndex,
+    misc::{
+        diff::{DiffOp, myers},
+        hash::Fnv1a,
+        parallelism::parallelism,
+        prng::SplitMix64,
+        template::{Template, TemplateGroup, TemplateReport},
+        token_borders::TokenBorders,
+        tokens::{Token, TokenCounts},
+    },
 };
 
-use super::{arc_str::ArcStr, cut_index::CutIndex};
+use super::arc_str::ArcStr;
 
 /// A cheap-to-clone container for storage and retrieval of log lines.
 ///
@@ -33,6 +61,14 @@ use super::{arc_str::ArcStr, cut_index::CutIndex};
 ///     assert_eq!(line.as_str(), "line 2");
 /// }
 /// ```
+///
+/// # Empty buffers
+///
+/// Lines are the pieces between `\n` separators, so the empty string holds a
+/// single empty line and `Buffer::new(String::new()).len()` is `1`. A buffer
+/// with no lines at all only comes from `slice` or `select` (e.g.
+/// `slice(0..0)` or `select([])`); it has a `len()` of `0` and its `as_str`,
+/// `join` and `to_string()` are empty.
 #[derive(Debug, Clone)]
 pub struct Buffer {
     astr: ArcStr,
@@ -42,22 +78,186 @@ pub struct Buffer {
 
 impl Buffer {
     /// Creates a new `Buffer` from a string.
+    ///
+    /// The line index is built in parallel when the content is at least
+    /// [`BufferBuilder::DEFAULT_PARALLEL_THRESHOLD`] bytes long and sequentially
+    /// otherwise. Use [`Buffer::builder`] to change the threshold.
     pub fn new(content: String) -> Buffer {
+        BufferBuilder::default().build(content)
+    }
+
+    /// Creates a new `Buffer` from a string with the given handling of a
+    /// trailing newline.
+    ///
+    /// ```
+    /// use analogz::containers::{Buffer, LineTermination};
+    ///
+    /// let content = "a\nb\n".to_string();
+    /// assert_eq!(Buffer::new_with(content.clone(), LineTermination::IncludeTrailingEmpty).len(), 3);
+    /// assert_eq!(Buffer::new_with(content, LineTermination::OmitTrailingEmpty).len(), 2);
+    /// ```
+    pub fn new_with(content: String, policy: LineTermination) -> Buffer {
+        BufferBuilder::default()
+            .line_termination(policy)
+            .build(content)
+    }
+
+    /// Creates a new `Buffer` from a string, indexing its lines on the current
+    /// thread.
+    pub fn new_sequential(content: String) -> Buffer {
+        Buffer {
+            index: CutIndex::build(&content, |c| c == &b'\n'),
+            astr: ArcStr::from(content),
+            select: None,
+        }
+    }
+
+    /// Creates a new `Buffer` from bytes that may not be valid UTF-8, replacing
+    /// invalid sequences with `U+FFFD`.
+    ///
+    /// Valid input is used without copying. Each replaced sequence becomes the
+    /// 3 bytes of `U+FFFD`, so after a replacement the byte offsets of the
+    /// buffer no longer match the original file.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::from_bytes_lossy(b"ok\nbad \xF0\x90 byte".to_vec());
+    /// assert_eq!(logs.get(1).unwrap().as_str(), "bad \u{FFFD} byte");
+    /// ```
+    pub fn from_bytes_lossy(bytes: Vec<u8>) -> Buffer {
+        let content = String::from_utf8(bytes)
+            .unwrap_or_else(|err| String::from_utf8_lossy(err.as_bytes()).into_owned());
+        Buffer::new(content)
+    }
+
+    /// Reads the file at `path` into a new `Buffer`.
+    ///
+    /// With the `flate2` feature, gzip files are detected by their magic bytes,
+    /// whatever their extension, and decompressed. The file is always read
+    /// into an owned string: it is not memory mapped, and a decompressed file
+    /// is held in memory in full.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file cannot be read or decompressed, or if its content is
+    /// not valid UTF-8 (with [`std::io::ErrorKind::InvalidData`]). Use
+    /// [`Buffer::from_bytes_lossy`] for files with invalid sequences.
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Buffer> {
+        let bytes = std::fs::read(path)?;
+        #[cfg(feature = "flate2")]
+        let bytes = if bytes.starts_with(&[0x1f, 0x8b]) {
+            let mut content = Vec::new();
+            flate2::read::MultiGzDecoder::new(bytes.as_slice()).read_to_end(&mut content)?;
+            content
+        } else {
+            bytes
+        };
+        let content = String::from_utf8(bytes)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Ok(Buffer::new(content))
+    }
+
+    /// Creates a new `Buffer` holding the given lines.
+    ///
+    /// The lines are joined with `\n` and indexed while the content is being
+    /// built, so there is no separate scan for newlines. No trailing newline is
+    /// added, and a line that itself contains `\n` becomes several lines. As
+    /// with `Buffer::new`, an empty iterator yields one empty line.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::from_lines(["line 1", "line 2"]);
+    /// assert_eq!(logs.len(), 2);
+    /// assert_eq!(logs.as_str(), "line 1\nline 2");
+    /// ```
+    pub fn from_lines(lines: impl IntoIterator<Item = impl AsRef<str>>) -> Buffer {
+        let mut content = String::new();
+        let mut cuts = Vec::new();
+        for (i, line) in lines.into_iter().enumerate() {
+            if i > 0 {
+                cuts.push(content.len());
+                content.push('\n');
+            }
+            let offset = content.len();
+            let line = line.as_ref();
+            cuts.extend(
+                line.bytes()
+                    .enumerate()
+                    .filter_map(|(i, b)| (b == b'\n').then_some(offset + i)),
+            );
+            content.push_str(line);
+        }
         Buffer {
-            index: CutIndex::build_par(&content, |c| c == &b'\n'),
+            index: CutIndex::from_cuts(cuts, content.len()),
             astr: ArcStr::from(content),
             select: None,
         }
     }
 
+    /// Returns a [`BufferBuilder`] to tune how a `Buffer` is created.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::builder()
+    ///     .parallel_threshold(1024)
+    ///     .build("line 1\nline 2".to_string());
+    /// assert_eq!(logs.len(), 2);
+    /// ```
+    pub fn builder() -> BufferBuilder {
+        BufferBuilder::default()
+    }
+
+    /// Controls the empty line that follows a trailing `\n`.
+    ///
+    /// `Buffer::new` treats `"a\nb\n"` as three lines, the last one empty. Passing
+    /// `false` drops that final empty line so the buffer holds only `a` and `b`;
+    /// passing `true` keeps the default behavior. Other empty lines, including
+    /// the single line of an empty string, are never dropped.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("a\nb\n".to_string());
+    /// assert_eq!(logs.len(), 3);
+    /// assert_eq!(logs.with_trailing_empty(false).len(), 2);
+    /// ```
+    pub fn with_trailing_empty(self, keep: bool) -> Buffer {
+        let is_trailing = |line: Line| line.start() > 0 && line.start() == self.astr.end();
+        match self.len().checked_sub(1) {
+            Some(last) if !keep && self.get(last).is_some_and(is_trailing) => self.slice(0..last),
+            _ => self,
+        }
+    }
+
     /// Returns the underlying string content as `&str`.
+    ///
+    /// The content is the contiguous span of the backing string covered by the
+    /// buffer. After a `select` of non-adjacent lines this span includes the
+    /// skipped lines; use [`Buffer::join`] or `to_string()` to get exactly the
+    /// visible lines.
     pub fn as_str(&self) -> &str {
-        let start = self.index.start(0).unwrap();
-        let end = self.index.end(self.index.len() - 1).unwrap();
-        &self.astr[start..end]
+        if self.is_empty() {
+            return "";
+        }
+        let (first, last) = match &self.select {
+            Some(select) => (
+                select.iter().copied().min().unwrap(),
+                select.iter().copied().max().unwrap(),
+            ),
+            None => (0, self.index.len() - 1),
+        };
+        let start = self.index.start(first).unwrap();
+        let end = self.index.end(last).unwrap();
+        &self.astr.as_str()[start..end]
     }
 
     /// Returns the number of lines in the log buffer.
+    ///
+    /// Content with `n` newlines has `n + 1` lines, so the empty string counts
+    /// as one (empty) line.
     pub fn len(&self) -> usize {
         if let Some(select) = &self.select {
             select.len()
@@ -67,6 +267,9 @@ impl Buffer {
     }
 
     /// Checks if the log buffer is empty (contains no lines).
+    ///
+    /// This is never the case for a buffer fresh from `Buffer::new`, even with
+    /// empty content; see [the empty buffer rules](Buffer#empty-buffers).
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
@@ -87,8 +290,31 @@ impl Buffer {
         }
     }
 
+    /// Returns the line number, in the content the buffer was created from, of
+    /// the line at `idx`, however many `slice` and `select` calls led to this
+    /// buffer. Returns `None` if `idx` is out of range.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("l0\nl1\nl2\nl3\nl4".to_string());
+    /// let filtered = logs.slice(1..).select([0, 2]).unwrap();
+    /// assert_eq!(filtered.original_index(1), Some(3));
+    /// ```
+    pub fn original_index(&self, idx: usize) -> Option<usize> {
+        let idx = match &self.select {
+            Some(select) => *select.get(idx)?,
+            None if idx < self.len() => idx,
+            None => return None,
+        };
+        Some(self.index.offset() + idx)
+    }
+
     /// Returns a slice of the log buffer for the given range of lines.
     ///
+    /// The range is clamped to the buffer, so `slice(2..)` or an end past
+    /// `len()` never panics; a range starting past the end yields an empty
+    /// buffer.
     ///
     /// ```
     /// use analogz::containers::Buffer;
@@ -99,7 +325,21 @@ impl Buffer {
     /// assert_eq!(middle_lines.get(0).unwrap().as_str(), "line 2");
     /// assert_eq!(middle_lines.get(1).unwrap().as_str(), "line 3");
     /// ```
-    pub fn slice(&self, rng: Range<usize>) -> Buffer {
+    pub fn slice(&self, rng: impl RangeBounds<usize>) -> Buffer {
+        let len = self.len();
+        let start = match rng.start_bound() {
+            Bound::Included(i) => *i,
+            Bound::Excluded(i) => i.saturating_add(1),
+            Bound::Unbounded => 0,
+        }
+        .min(len);
+        let end = match rng.end_bound() {
+            Bound::Included(i) => i.saturating_add(1),
+            Bound::Excluded(i) => *i,
+            Bound::Unbounded => len,
+        }
+        .clamp(start, len);
+        let rng = start..end;
         if let Some(select) = self.select.clone() {
             Self {
                 astr: self.astr.clone(),
@@ -109,12 +349,97 @@ impl Buffer {
         } else {
             Self {
                 astr: self.astr.clone(),
-                index: self.index.slice(rng.start..rng.end),
+                index: self.index.slice(rng),
                 select: None,
             }
         }
     }
 
+    /// Returns the buffer without its first `skip_head` and last `skip_tail`
+    /// lines, e.g. to drop a header row and a summary footer.
+    ///
+    /// Skipping more lines than the buffer holds yields an empty buffer. The
+    /// empty line after a trailing `\n` counts as a line; call
+    /// [`Buffer::with_trailing_empty`] first to ignore it.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("name,value\na,1\nb,2\ntotal,3".to_string());
+    /// let body = logs.body(1, 1);
+    /// assert_eq!(body.join("\n"), "a,1\nb,2");
+    /// assert!(logs.body(3, 3).is_empty());
+    /// ```
+    pub fn body(&self, skip_head: usize, skip_tail: usize) -> Buffer {
+        let start = skip_head.min(self.len());
+        let end = self.len().saturating_sub(skip_tail).max(start);
+        self.slice(start..end)
+    }
+
+    /// Returns the groups of `size` consecutive lines starting every `step`
+    /// lines, each as a `slice` of the buffer.
+    ///
+    /// When the lines past the last full group are not covered by any group,
+    /// `partial` decides whether they are yielded as one shorter final group.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` or `step` is zero.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("a\nb\nc\nd\ne".to_string());
+    /// let groups = logs.line_windows(2, 2, true).map(|w| w.join("")).collect::<Vec<_>>();
+    /// assert_eq!(groups, ["ab", "cd", "e"]);
+    /// assert_eq!(logs.line_windows(2, 2, false).count(), 2);
+    /// ```
+    pub fn line_windows(
+        &self,
+        size: usize,
+        step: usize,
+        partial: bool,
+    ) -> impl Iterator<Item = Buffer> {
+        assert!(size > 0, "window size must be positive");
+        assert!(step > 0, "window step must be positive");
+        let len = self.len();
+        (0..len)
+            .step_by(step)
+            .take_while(move |start| {
+                let covered = start
+                    .checked_sub(step)
+                    .map_or(0, |prev| prev.saturating_add(size));
+                start.saturating_add(size) <= len || (partial && covered < len)
+            })
+            .map(move |start| self.slice(start..start.saturating_add(size)))
+    }
+
+    /// Copies the lines of the buffer into a new, independent backing string.
+    ///
+    /// A `slice` or `select` of a buffer keeps the whole content it was
+    /// created from alive. Compacting a small selection lets that content be
+    /// freed once the original buffers are dropped. The lines of the result
+    /// are the same, but their offsets are in the new content, so they no
+    /// longer relate to the source (e.g. with [`ArcStr::relative_position`]
+    /// or [`Buffer::original_index`]).
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("INFO a\nERROR b\nINFO c\nERROR d".to_string());
+    /// let errors = logs.select([1, 3]).unwrap().compact();
+    /// drop(logs);
+    /// assert_eq!(errors.as_str(), "ERROR b\nERROR d");
+    /// ```
+    pub fn compact(&self) -> Buffer {
+        let compacted = Buffer::from_lines(self.iter().map(Line::into_arc_str));
+        if self.is_empty() {
+            compacted.slice(0..0)
+        } else {
+            compacted
+        }
+    }
+
     /// Selects specific lines from the log buffer based on the provided indices.
     ///
     ///
@@ -157,6 +482,257 @@ impl Buffer {
         })
     }
 
+    /// Selects the lines at `indices`, like [`Buffer::select`], taking the
+    /// indices as an `ArcSlice`, e.g. `Column::present_indices`.
+    ///
+    /// On a buffer without a selection the slice is shared instead of copied.
+    ///
+    /// ```
+    /// use analogz::containers::{ArcSlice, Buffer};
+    ///
+    /// let logs = Buffer::new("line 1\nline 2\nline 3".to_string());
+    /// let selected = logs.select_slice(&ArcSlice::new(vec![2, 0])).unwrap();
+    /// assert_eq!(selected.join(","), "line 3,line 1");
+    /// ```
+    pub fn select_slice(&self, indices: &ArcSlice<usize>) -> Result<Buffer, InvalidIndexError> {
+        match &self.select {
+            Some(select) => Ok(Self {
+                astr: self.astr.clone(),
+                index: self.index.clone(),
+                select: Some(select.select(indices.iter().copied())?),
+            }),
+            None => {
+                if let Some(idx) = indices.iter().find(|idx| **idx >= self.len()) {
+                    return Err(InvalidIndexError(*idx));
+                }
+                Ok(Self {
+                    astr: self.astr.clone(),
+                    index: self.index.clone(),
+                    select: Some(indices.clone()),
+                })
+            }
+        }
+    }
+
+    /// Returns a random sample of `n` lines, preserving their original order.
+    ///
+    /// The sample is drawn with reservoir sampling and is deterministic for a
+    /// given `seed`. If `n` is greater than or equal to the number of lines,
+    /// all lines are returned.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("line 1\nline 2\nline 3\nline 4".to_string());
+    /// let sample = logs.sample(2, 42);
+    /// assert_eq!(sample.len(), 2);
+    /// ```
+    pub fn sample(&self, n: usize, seed: u64) -> Buffer {
+        let n = n.min(self.len());
+        let mut rng = SplitMix64::new(seed);
+        let mut reservoir = (0..n).collect_vec();
+        for i in n..self.len() {
+            let j = rng.below(i + 1);
+            if j < n {
+                reservoir[j] = i;
+            }
+        }
+        reservoir.sort_unstable();
+        self.select(reservoir)
+            .expect("reservoir indices are within the buffer")
+    }
+
+    /// Concatenates the lines of the buffer, placing `sep` between them.
+    ///
+    /// Unlike [`Buffer::as_str`], only the visible lines are included, which
+    /// matters for buffers created by `select`.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("line 1\nline 2\nline 3".to_string());
+    /// let selected = logs.select([0, 2]).unwrap();
+    /// assert_eq!(selected.join(" | "), "line 1 | line 3");
+    /// ```
+    pub fn join(&self, sep: &str) -> String {
+        self.iter().map(Line::into_arc_str).join(sep)
+    }
+
+    /// Soft-wraps the lines at `width` characters for display.
+    ///
+    /// Yields `(line_index, segment)` pairs where `line_index` is the position of
+    /// the line in the buffer and each segment holds at most `width` characters.
+    /// Segments are cut on char boundaries and concatenating the segments of a
+    /// line reproduces it; an empty line yields a single empty segment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is zero.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("abcde\nfg".to_string());
+    /// let wrapped = logs
+    ///     .wrapped(2)
+    ///     .map(|(idx, seg)| (idx, seg.as_str().to_string()))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     wrapped,
+    ///     vec![(0, "ab".into()), (0, "cd".into()), (0, "e".into()), (1, "fg".into())]
+    /// );
+    /// ```
+    pub fn wrapped(&self, width: usize) -> impl Iterator<Item = (usize, ArcStr)> {
+        assert!(width > 0, "wrap width must be greater than zero");
+        self.iter().enumerate().flat_map(move |(idx, line)| {
+            let line = line.into_arc_str();
+            let mut cuts = line
+                .as_str()
+                .char_indices()
+                .map(|(i, _)| i)
+                .step_by(width)
+                .skip(1)
+                .collect_vec();
+            cuts.push(line.len());
+            let mut start = 0;
+            cuts.into_iter().map(move |end| {
+                let segment = line.slice(start..end);
+                start = end;
+                (idx, segment)
+            })
+        })
+    }
+
+    /// Returns an iterator over the lines including their `\n` terminator.
+    ///
+    /// Every line that is followed by a newline in the content is yielded with
+    /// it; the last line of the content has no terminator.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("a\nb".to_string());
+    /// let lines = logs.iter_with_terminators().collect::<Vec<_>>();
+    /// assert_eq!(lines, ["a\n", "b"]);
+    /// ```
+    pub fn iter_with_terminators(&self) -> impl Iterator<Item = ArcStr> {
+        let astr = self.astr.clone();
+        self.iter().map(move |line| {
+            let end = (line.end() + 1).min(astr.end());
+            astr.slice(line.start()..end)
+        })
+    }
+
+    /// Returns an iterator over the byte range of each line in the content.
+    ///
+    /// The ranges are offsets into the content the buffer was created from and
+    /// are read straight from the line index, without building `Line`s.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("ab\ncde\nf".to_string());
+    /// let ranges = logs.select([2, 0]).unwrap().byte_ranges().collect::<Vec<_>>();
+    /// assert_eq!(ranges, [7..8, 0..2]);
+    /// ```
+    pub fn byte_ranges(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        (0..self.len()).map(|idx| {
+            let idx = match &self.select {
+                Some(select) => select[idx],
+                None => idx,
+            };
+            self.index
+                .range(idx)
+                .expect("line index is within the buffer")
+        })
+    }
+
+    /// Returns the byte range of the line at `idx` including its `\n`
+    /// terminator, or up to the end of the content for the last line.
+    ///
+    /// Like [`Buffer::byte_ranges`], offsets are relative to the content the
+    /// buffer was created from, so they can be used to seek in the original
+    /// file. Lines are split on `\n` only, so the `\r` of a `\r\n` terminator is
+    /// already part of the line and is covered as well.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("ab\ncd".to_string());
+    /// assert_eq!(logs.line_span_with_terminator(0), Some(0..3));
+    /// assert_eq!(logs.line_span_with_terminator(1), Some(3..5));
+    /// assert_eq!(logs.line_span_with_terminator(2), None);
+    /// ```
+    pub fn line_span_with_terminator(&self, idx: usize) -> Option<Range<usize>> {
+        let line = self.get(idx)?;
+        let end = (line.end() + 1).min(self.astr.end());
+        Some(line.start()..end)
+    }
+
+    /// Returns the lines overlapping the byte range `bytes`, with their indices
+    /// in this buffer. Lines are yielded in full, even when `bytes` only covers
+    /// part of them, and the `\n` ending a line counts as part of it.
+    ///
+    /// Offsets are in the content the buffer was created from, as given by
+    /// [`Line::start`], and `bytes` is clamped to the content, so a range
+    /// starting at its end overlaps no line. The first and last lines are found
+    /// by binary search, so only the lines in range are touched; after a
+    /// `select`, every selected line is checked instead.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("line 1\nline 2\nline 3".to_string());
+    /// let lines = logs
+    ///     .lines_in_byte_range(3..9)
+    ///     .map(|(idx, line)| (idx, line.to_string()))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(lines, [(0, "line 1".to_string()), (1, "line 2".to_string())]);
+    /// ```
+    pub fn lines_in_byte_range(&self, bytes: Range<usize>) -> impl Iterator<Item = (usize, Line)> {
+        let bytes = bytes.start..bytes.end.min(self.astr.end());
+        let idxs = match &self.select {
+            None => self.index.overlapping(bytes.clone()),
+            Some(_) => 0..self.len(),
+        };
+        let selected = self.select.is_some();
+        idxs.filter_map(|idx| Some((idx, self.get(idx)?)))
+            .filter(move |(_, line)| {
+                !selected
+                    || (!bytes.is_empty() && line.start() < bytes.end && line.end() >= bytes.start)
+            })
+    }
+
+    /// Computes a line-level diff from `self` to `other`.
+    ///
+    /// `Equal` and `Delete` ops hold line indices of `self`, `Insert` ops line
+    /// indices of `other`. Lines are hashed once up front so that most
+    /// comparisons of the Myers diff are cheap.
+    ///
+    /// ```
+    /// use analogz::{containers::Buffer, misc::diff::DiffOp};
+    ///
+    /// let before = Buffer::new("start\nload config\nready".to_string());
+    /// let after = Buffer::new("start\nready\nserving".to_string());
+    /// assert_eq!(
+    ///     before.diff(&after),
+    ///     [DiffOp::Equal(0), DiffOp::Delete(1), DiffOp::Equal(2), DiffOp::Insert(2)]
+    /// );
+    /// ```
+    pub fn diff(&self, other: &Buffer) -> Vec<DiffOp> {
+        let hashed = |buffer: &Buffer| {
+            let state = BuildHasherDefault::<DefaultHasher>::default();
+            buffer
+                .iter()
+                .map(|line| (state.hash_one(line.as_str()), line))
+                .collect_vec()
+        };
+        let (old, new) = (hashed(self), hashed(other));
+        myers(old.len(), new.len(), |i, j| {
+            old[i].0 == new[j].0 && old[i].1.as_str() == new[j].1.as_str()
+        })
+    }
+
     /// Returns an iterator over all lines in the log buffer.
     ///
     ///
@@ -198,40 +774,844 @@ impl Buffer {
         self.iter().map(f).collect_vec().into()
     }
 
-    /// Applies a function to each line in the buffer in parallel, producing an `ArcSlice`.
-    ///
-    /// This method divides the buffer into chunks and processes each chunk in parallel
-    /// using multiple threads. The function `f` is applied to each line, and the results
-    /// are collected into an `ArcSlice`.
-    ///
-    ///
-    /// ```
-    /// use analogz::containers::Buffer;
-    ///
-    /// let logs = Buffer::new("line 1\nline 2\nline 3".to_string());
-    ///
-    /// let uppercased = logs.map(|line| line.as_str().to_uppercase());
-    /// assert_eq!(uppercased.get(0).unwrap().as_str(), "LINE 1");
-    /// assert_eq!(uppercased.get(1).unwrap().as_str(), "LINE 2");
-    /// assert_eq!(uppercased.get(2).unwrap().as_str(), "LINE 3");
-    /// ```
-    pub fn par_map<F, O>(&self, f: F) -> ArcSlice<O>
-    where
-        O: Send,
-        F: Fn(Line) -> O + Send + Clone,
-    {
-        let slice_size = (self.len() / num_cpus::get()).max(1);
-        std::thread::scope(|scope| {
-            SteppedRange::new(0, self.len(), slice_size)
-                .map(|offset| {
-                    let f = f.clone();
-                    scope.spawn(move || self.slice(offset..offset + slice_size).into_iter().map(f))
-                })
-                .filter_map(|hndl| hndl.join().ok())
-                .flatten()
-                .collect_vec()
-        })
-        .into()
+    /// Applies a function to each line in the buffer in parallel, producing an `ArcSlice`.
+    ///
+    /// This method divides the buffer into chunks and processes each chunk in parallel
+    /// using multiple threads. The function `f` is applied to each line, and the results
+    /// are collected into an `ArcSlice`. The number of threads is controlled by
+    /// [`set_parallelism`](crate::set_parallelism).
+    ///
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("line 1\nline 2\nline 3".to_string());
+    ///
+    /// let uppercased = logs.map(|line| line.as_str().to_uppercase());
+    /// assert_eq!(uppercased.get(0).unwrap().as_str(), "LINE 1");
+    /// assert_eq!(uppercased.get(1).unwrap().as_str(), "LINE 2");
+    /// assert_eq!(uppercased.get(2).unwrap().as_str(), "LINE 3");
+    /// ```
+    pub fn par_map<F, O>(&self, f: F) -> ArcSlice<O>
+    where
+        O: Send,
+        F: Fn(Line) -> O + Send + Clone,
+    {
+        self.par_map_with_progress(f, |_, _| {})
+    }
+
+    /// Same as [`Buffer::par_map`], pairing each result with the byte range of
+    /// its line, so values can be written back at their position in the text.
+    ///
+    /// Ranges are offsets in the content the buffer was built from, as given by
+    /// [`Line::start`] and [`Line::end`], and exclude the line terminator.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("ok\nerror".to_string());
+    /// let lengths = logs.map_ranges(|line| line.len());
+    /// assert_eq!(lengths.as_slice(), &[(0..2, 2), (3..8, 5)]);
+    /// ```
+    pub fn map_ranges<F, O>(&self, f: F) -> ArcSlice<(Range<usize>, O)>
+    where
+        O: Send,
+        F: Fn(&Line) -> O + Send + Clone,
+    {
+        self.par_map(move |line| (line.start()..line.end(), f(&line)))
+    }
+
+    /// Same as [`Buffer::par_map`], reporting progress as chunks complete.
+    ///
+    /// `on_progress(done, total)` is called from the worker threads each time a
+    /// chunk is mapped, where `done` is the number of lines mapped so far and
+    /// `total` the number of lines in the buffer. Calls may come from several
+    /// threads at once, and the last one reports `done == total`.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// let logs = Buffer::new("line 1\nline 2\nline 3".to_string());
+    /// let done = AtomicUsize::new(0);
+    /// let lengths = logs.par_map_with_progress(
+    ///     |line| line.len(),
+    ///     |d, _total| {
+    ///         done.fetch_max(d, Ordering::Relaxed);
+    ///     },
+    /// );
+    /// assert_eq!(lengths.len(), 3);
+    /// assert_eq!(done.load(Ordering::Relaxed), 3);
+    /// ```
+    pub fn par_map_with_progress<F, O, P>(&self, f: F, on_progress: P) -> ArcSlice<O>
+    where
+        O: Send,
+        F: Fn(Line) -> O + Send + Clone,
+        P: Fn(usize, usize) + Sync,
+    {
+        self.par_map_with_threads(parallelism(), f, on_progress)
+    }
+
+    /// Same as [`Buffer::par_map_with_progress`], splitting the lines over
+    /// `threads` threads instead of the global [`parallelism`].
+    #[cfg_attr(feature = "no-threads", allow(unused_variables))]
+    pub(crate) fn par_map_with_threads<F, O, P>(
+        &self,
+        threads: usize,
+        f: F,
+        on_progress: P,
+    ) -> ArcSlice<O>
+    where
+        O: Send,
+        F: Fn(Line) -> O + Send + Clone,
+        P: Fn(usize, usize) + Sync,
+    {
+        #[cfg(feature = "no-threads")]
+        {
+            let mapped = self.map(f);
+            on_progress(mapped.len(), mapped.len());
+            mapped
+        }
+        #[cfg(not(feature = "no-threads"))]
+        {
+            let total = self.len();
+            let done = AtomicUsize::new(0);
+            std::thread::scope(|scope| {
+                chunk_ranges(total, threads)
+                    .map(|rng| {
+                        let f = f.clone();
+                        let (done, on_progress) = (&done, &on_progress);
+                        scope.spawn(move || {
+                            let mapped = self.slice(rng).into_iter().map(f).collect_vec();
+                            let done =
+                                done.fetch_add(mapped.len(), Ordering::Relaxed) + mapped.len();
+                            on_progress(done, total);
+                            mapped
+                        })
+                    })
+                    .collect_vec()
+                    .into_iter()
+                    .flat_map(|hndl| hndl.join().unwrap_or_else(|err| panic::resume_unwind(err)))
+                    .collect_vec()
+            })
+            .into()
+        }
+    }
+
+    /// Parses every line into a `T` in parallel.
+    ///
+    /// Lines that [`FromLine::from_line`] rejects are kept as `None`, so the
+    /// result is aligned with the lines of the buffer.
+    pub fn parse_records<T>(&self) -> ArcSlice<Option<T>>
+    where
+        T: FromLine + Send,
+    {
+        self.par_map(|line| T::from_line(&line))
+    }
+
+    /// Matches every line against a set of labeled regexes in parallel.
+    ///
+    /// Returns one boolean column per label, in the order of `labels`, telling
+    /// for each line whether the label's regex matched it. A line may carry any
+    /// number of labels. Each regex is matched as given, so flags set through
+    /// `RegexBuilder` such as case insensitivity are honored.
+    ///
+    /// ```
+    /// use analogz::containers::{Buffer, Regex};
+    ///
+    /// let logs = Buffer::new("auth failed\ndb timeout\nauth db down".to_string());
+    /// let labels = [
+    ///     ("auth".to_string(), Regex::new("auth").unwrap()),
+    ///     ("db".to_string(), Regex::new(r"\bdb\b").unwrap()),
+    /// ];
+    /// let columns = logs.classify(&labels);
+    /// assert_eq!(columns[0].as_slice(), &[true, false, true]);
+    /// assert_eq!(columns[1].as_slice(), &[false, true, true]);
+    /// ```
+    pub fn classify(&self, labels: &[(String, Regex)]) -> Vec<ArcSlice<bool>> {
+        let matches = self.par_map(|line| {
+            labels
+                .iter()
+                .map(|(_, re)| re.is_match(line.as_str()))
+                .collect_vec()
+        });
+        (0..labels.len())
+            .map(|label| matches.iter().map(|m| m[label]).collect_vec().into())
+            .collect()
+    }
+
+    /// Returns the first line matching `re` along with its index in the buffer.
+    ///
+    /// Lines are scanned in order on the current thread and the scan stops at
+    /// the first match, which makes this cheap for needles near the top. For a
+    /// buffer created by `select`, the index is the position in the selection,
+    /// so `get(idx)` returns the same line.
+    ///
+    /// ```
+    /// use analogz::containers::{Buffer, Regex};
+    ///
+    /// let logs = Buffer::new("INFO start\nERROR boom\nERROR again".to_string());
+    /// let (idx, line) = logs.find_first(&Regex::new("ERROR").unwrap()).unwrap();
+    /// assert_eq!(idx, 1);
+    /// assert_eq!(line.as_str(), "ERROR boom");
+    /// ```
+    pub fn find_first(&self, re: &Regex) -> Option<(usize, Line)> {
+        self.iter()
+            .enumerate()
+            .find(|(_, line)| re.is_match(line.as_str()))
+    }
+
+    /// Returns the byte ranges matched by `re` in each line, for the lines with
+    /// at least one match.
+    ///
+    /// Yields `(line_index, ranges)` pairs in line order, where the ranges are
+    /// relative to the start of the line. Lines are searched in parallel.
+    ///
+    /// ```
+    /// use analogz::containers::{Buffer, Regex};
+    ///
+    /// let logs = Buffer::new("id=1 id=2\nnothing\nid=3".to_string());
+    /// let ranges = logs
+    ///     .match_ranges(&Regex::new(r"id=\d").unwrap())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(ranges, [(0, vec![0..4, 5..9]), (2, vec![0..4])]);
+    /// ```
+    pub fn match_ranges(&self, re: &Regex) -> impl Iterator<Item = (usize, Vec<Range<usize>>)> {
+        self.par_map(|line| re.find_iter(line.as_str()).map(|m| m.range()).collect_vec())
+            .into_iter()
+            .enumerate()
+            .filter(|(_, ranges)| !ranges.is_empty())
+    }
+
+    /// Returns the lines matching `re` with the text of its capture groups.
+    ///
+    /// Yields `(line_index, line, captures)` in line order, where `captures`
+    /// holds one entry per capture group of `re` (group 0, the whole match,
+    /// excluded) for the first match in the line, `None` for groups that did
+    /// not participate. The captures are slices of the line, so they keep their
+    /// offsets. Lines are searched lazily, on the calling thread.
+    ///
+    /// ```
+    /// use analogz::containers::{Buffer, Regex};
+    ///
+    /// let logs = Buffer::new("user=bob id=7\nnothing\nuser=eve id=9".to_string());
+    /// let re = Regex::new(r"user=(\w+) id=(\d+)").unwrap();
+    /// let (idx, _, captures) = logs.enumerate_matches(&re).nth(1).unwrap();
+    /// assert_eq!(idx, 2);
+    /// assert_eq!(captures[0].as_ref().unwrap().as_str(), "eve");
+    /// ```
+    pub fn enumerate_matches(
+        &self,
+        re: &Regex,
+    ) -> impl Iterator<Item = (usize, Line, Vec<Option<ArcStr>>)> {
+        self.iter().enumerate().filter_map(|(idx, line)| {
+            let captures = re.captures(line.as_str())?;
+            let groups = captures
+                .iter()
+                .skip(1)
+                .map(|m| m.map(|m| line.slice(m.range())))
+                .collect_vec();
+            Some((idx, line, groups))
+        })
+    }
+
+    /// Selects the lines that contain at least one of the `keywords` as a token.
+    ///
+    /// Lines are split into tokens at ASCII whitespace and punctuation (see
+    /// [`TokenBorders`]), so a keyword only matches a whole token: `error` is
+    /// found in `an error here` but not in `errorless`. Lines are tokenized in
+    /// parallel.
+    ///
+    /// ```
+    /// use analogz::containers::{ArcStr, Buffer};
+    /// use std::collections::HashSet;
+    ///
+    /// let logs = Buffer::new("errorless log\nan error here\nwarn: disk".to_string());
+    /// let keywords = HashSet::from([ArcStr::from("error"), ArcStr::from("warn")]);
+    /// let found = logs.grep_tokens(&keywords);
+    /// assert_eq!(found.to_string(), "an error here\nwarn: disk");
+    /// ```
+    pub fn grep_tokens(&self, keywords: &HashSet<ArcStr>) -> Buffer {
+        let keep = self.par_map(|line| {
+            let haystack = line.as_str();
+            TokenBorders::new(haystack)
+                .tuples()
+                .any(|(start, end)| start < end && keywords.contains(&haystack[start..end]))
+        });
+        self.select(keep.iter().positions(|keep| *keep))
+            .expect("positions are within the buffer")
+    }
+
+    /// Returns the lines containing `pattern`, ignoring ASCII case.
+    ///
+    /// This is a literal search: unlike a regex, `pattern` needs no escaping
+    /// and no `(?i)` flag. See [`CaseInsensitive`] for how non-ASCII chars
+    /// are compared. An empty `pattern` selects no lines.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("ERROR boom\nINFO ok\nerror: retry".to_string());
+    /// assert_eq!(logs.grep_ci("error").to_string(), "ERROR boom\nerror: retry");
+    /// ```
+    pub fn grep_ci(&self, pattern: &str) -> Buffer {
+        let keep = self.par_map(|line| line.contains(CaseInsensitive(pattern)));
+        self.select(keep.iter().positions(|keep| *keep))
+            .expect("positions are within the buffer")
+    }
+
+    /// Returns the lines matched by every regex of `res`, searching lines in
+    /// parallel.
+    ///
+    /// The regexes are tried in order and a line is rejected at the first one
+    /// that does not match, so put the most selective first. With no regex,
+    /// every line is kept.
+    ///
+    /// ```
+    /// use analogz::containers::{Buffer, Regex};
+    ///
+    /// let logs = Buffer::new("user=alice status=500\nuser=bob status=500".to_string());
+    /// let res = [Regex::new("status=500").unwrap(), Regex::new("user=alice").unwrap()];
+    /// assert_eq!(logs.grep_all(&res).to_string(), "user=alice status=500");
+    /// ```
+    pub fn grep_all(&self, res: &[Regex]) -> Buffer {
+        let keep = self.par_map(|line| res.iter().all(|re| re.is_match(line.as_str())));
+        self.select(keep.iter().positions(|keep| *keep))
+            .expect("positions are within the buffer")
+    }
+
+    /// Returns the buffer without repeated lines, keeping the first occurrence
+    /// of each distinct line in its original position.
+    ///
+    /// Lines are compared by content. Every distinct line is kept in a set while
+    /// scanning, so memory grows with the number of distinct lines; the set
+    /// shares the buffer's string and only holds handles to it.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("a\nb\na\nc\nb".to_string());
+    /// assert_eq!(logs.unique_lines().join(","), "a,b,c");
+    /// ```
+    pub fn unique_lines(&self) -> Buffer {
+        let mut seen = HashSet::new();
+        self.select(
+            self.iter()
+                .positions(|line| seen.insert(line.into_arc_str())),
+        )
+        .expect("positions are within the buffer")
+    }
+
+    /// Hashes every line in parallel into a key for joins and deduplication.
+    ///
+    /// Lines are hashed with [`Fnv1a`], so the hashes are stable across runs.
+    /// With `normalize`, leading and trailing whitespace is ignored, internal
+    /// runs of whitespace count as a single space and letters are lowercased
+    /// (see `char::to_lowercase`) before hashing, without allocating.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("  Disk  FULL \ndisk full\ndisk full".to_string());
+    /// let exact = logs.line_hashes(false);
+    /// assert_ne!(exact[0], exact[1]);
+    /// assert_eq!(exact[1], exact[2]);
+    /// let normalized = logs.line_hashes(true);
+    /// assert_eq!(normalized[0], normalized[1]);
+    /// ```
+    pub fn line_hashes(&self, normalize: bool) -> ArcSlice<u64> {
+        self.par_map(move |line| {
+            let mut hasher = Fnv1a::default();
+            if normalize {
+                let mut buf = [0; 4];
+                for (i, word) in line.as_str().split_whitespace().enumerate() {
+                    if i > 0 {
+                        hasher.write(b" ");
+                    }
+                    for c in word.chars().flat_map(char::to_lowercase) {
+                        hasher.write(c.encode_utf8(&mut buf).as_bytes());
+                    }
+                }
+            } else {
+                hasher.write(line.as_str().as_bytes());
+            }
+            hasher.finish()
+        })
+    }
+
+    /// Returns the number of bytes of the lines, without their terminators.
+    pub fn byte_len(&self) -> usize {
+        self.par_map(|line| line.len()).iter().sum()
+    }
+
+    /// Returns `true` if a line is terminated by `\r\n`. The `\r` is kept at
+    /// the end of such lines.
+    ///
+    /// A `\r` ending the last line of the content is not followed by `\n`, so
+    /// it does not count.
+    pub fn has_crlf(&self) -> bool {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let content = self.astr.as_arc_str().as_bytes();
+        let found = AtomicBool::new(false);
+        self.par_for_each(|_, line| {
+            if !found.load(Ordering::Relaxed)
+                && line.as_str().ends_with('\r')
+                && content.get(line.end()) == Some(&b'\n')
+            {
+                found.store(true, Ordering::Relaxed);
+            }
+        });
+        found.into_inner()
+    }
+
+    /// Returns an overview of the buffer, the first thing to look at on an
+    /// unknown log. See [`BufferSummary`] for the fields.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let summary = Buffer::new("a\nbb\na".to_string()).summary();
+    /// assert_eq!(summary.line_count, 3);
+    /// assert_eq!(summary.distinct_lines, 2);
+    /// ```
+    pub fn summary(&self) -> BufferSummary {
+        let line_count = self.len();
+        let byte_len = self.byte_len();
+        BufferSummary {
+            line_count,
+            byte_len,
+            distinct_lines: self.iter().map(|line| line.into_arc_str()).unique().count(),
+            avg_line_len: if line_count == 0 {
+                0.0
+            } else {
+                byte_len as f64 / line_count as f64
+            },
+            has_crlf: self.has_crlf(),
+        }
+    }
+
+    /// Counts the tokens of each kind of every line, in parallel. See
+    /// [`ArcStr::token_class_counts`].
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("GET /a 200\n!!! ???".to_string());
+    /// let counts = logs.token_class_counts();
+    /// assert_eq!(counts[0].words(), 3);
+    /// assert_eq!(counts[1].symbolic, 6);
+    /// ```
+    pub fn token_class_counts(&self) -> ArcSlice<TokenCounts> {
+        self.par_map(|line| line.token_class_counts())
+    }
+
+    /// Buckets the lines by their number of content tokens and their first
+    /// content token, a cheap prefilter before comparing lines in detail.
+    ///
+    /// Content tokens are those of [`ArcStr::content_tokens`], so whitespace is
+    /// not counted. Keys are computed in parallel; lines without content
+    /// tokens share the `(0, "")` bucket. The indices of each bucket are in
+    /// increasing order.
+    ///
+    /// ```
+    /// use analogz::containers::{ArcStr, Buffer};
+    ///
+    /// let logs = Buffer::new("GET /a 200\nGET /b 404\nPOST /a 201".to_string());
+    /// let buckets = logs.prebucket();
+    /// assert_eq!(buckets.len(), 2);
+    /// assert_eq!(buckets[&(4, ArcStr::from("GET"))].as_slice(), &[0, 1]);
+    /// ```
+    pub fn prebucket(&self) -> HashMap<(usize, ArcStr), ArcSlice<usize>> {
+        let keys = self.par_map(|line| {
+            let mut tokens = line
+                .content_tokens()
+                .filter_map(|token| token.as_arc_str().cloned());
+            match tokens.next() {
+                Some(first) => (tokens.count() + 1, first),
+                None => (0, ArcStr::from("")),
+            }
+        });
+        let mut buckets: HashMap<(usize, ArcStr), Vec<usize>> = HashMap::new();
+        for (idx, key) in keys.iter().enumerate() {
+            buckets.entry(key.clone()).or_default().push(idx);
+        }
+        buckets
+            .into_iter()
+            .map(|(key, lines)| (key, lines.into()))
+            .collect()
+    }
+
+    /// Groups the lines that only differ in their numbers, and returns the
+    /// template of each group.
+    ///
+    /// Lines are tokenized in parallel; two lines are grouped when they have
+    /// the same tokens once every numeric token is ignored. The template of a
+    /// group is [`Template::from_lines_iter`] of its lines, so its numbers
+    /// widen to ranges.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("req 1 ok\nreq 2 ok\nstartup\nreq 3 ok".to_string());
+    /// let top = logs.group_by_template().top(1);
+    /// assert_eq!((top[0].pattern.as_str(), top[0].count), ("req <1-3> ok", 3));
+    /// ```
+    pub fn group_by_template(&self) -> TemplateReport {
+        let keys = self.par_map(|line| {
+            line.tokenize()
+                .filter_map(|token| match token {
+                    Token::Start | Token::End => None,
+                    Token::Numeric(_) => Some(None),
+                    token => Some(token.as_arc_str().cloned()),
+                })
+                .collect_vec()
+        });
+        let mut groups: HashMap<&[Option<ArcStr>], Vec<usize>> = HashMap::new();
+        let mut order = Vec::new();
+        for (idx, key) in keys.iter().enumerate() {
+            groups
+                .entry(key.as_slice())
+                .or_insert_with(|| {
+                    order.push(key.as_slice());
+                    Vec::new()
+                })
+                .push(idx);
+        }
+        TemplateReport::new(
+            order
+                .into_iter()
+                .map(|key| {
+                    let lines = groups.remove(key).expect("every key has a group");
+                    let line = |idx: &usize| {
+                        self.get(*idx)
+                            .expect("line index is within the buffer")
+                            .into_arc_str()
+                    };
+                    TemplateGroup {
+                        template: Template::from_lines_iter(lines.iter().map(line)),
+                        example: line(&lines[0]),
+                        lines,
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Applies a fallible function to each line in parallel.
+    ///
+    /// Returns the mapped values in line order, or the error of the first line
+    /// (in line order) for which `f` failed. Like [`Buffer::par_map`], a panic in
+    /// `f` is propagated to the caller.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("1\n2\n3".to_string());
+    /// let numbers = logs.try_par_map(|line| line.as_str().parse::<u32>()).unwrap();
+    /// assert_eq!(numbers.as_slice(), &[1, 2, 3]);
+    ///
+    /// let logs = Buffer::new("1\ntwo\n3".to_string());
+    /// assert!(logs.try_par_map(|line| line.as_str().parse::<u32>()).is_err());
+    /// ```
+    pub fn try_par_map<F, O, E>(&self, f: F) -> Result<ArcSlice<O>, E>
+    where
+        O: Send,
+        E: Send,
+        F: Fn(Line) -> Result<O, E> + Send + Clone,
+    {
+        self.try_par_map_with_threads(parallelism(), f)
+    }
+
+    /// Same as [`Buffer::try_par_map`], splitting the lines over `threads`
+    /// threads instead of the global [`parallelism`].
+    #[cfg_attr(feature = "no-threads", allow(unused_variables))]
+    pub(crate) fn try_par_map_with_threads<F, O, E>(
+        &self,
+        threads: usize,
+        f: F,
+    ) -> Result<ArcSlice<O>, E>
+    where
+        O: Send,
+        E: Send,
+        F: Fn(Line) -> Result<O, E> + Send + Clone,
+    {
+        #[cfg(feature = "no-threads")]
+        {
+            self.iter()
+                .map(f)
+                .collect::<Result<Vec<_>, _>>()
+                .map(ArcSlice::from)
+        }
+        #[cfg(not(feature = "no-threads"))]
+        {
+            std::thread::scope(|scope| {
+                chunk_ranges(self.len(), threads)
+                    .map(|rng| {
+                        let f = f.clone();
+                        scope.spawn(move || {
+                            self.slice(rng)
+                                .into_iter()
+                                .map(f)
+                                .collect::<Result<Vec<_>, _>>()
+                        })
+                    })
+                    .collect_vec()
+                    .into_iter()
+                    .map(|hndl| hndl.join().unwrap_or_else(|err| panic::resume_unwind(err)))
+                    .flatten_ok()
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .map(ArcSlice::from)
+        }
+    }
+
+    /// Applies a fallible function to every line in parallel, keeping going
+    /// past failures.
+    ///
+    /// Returns a column with the value of each line, `None` where `f` failed,
+    /// and the `(line_index, error)` pairs of the failed lines in order.
+    /// Unlike [`Buffer::try_par_map`], which stops at the first error, this
+    /// reports every failing line.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("12\nabc\n7".to_string());
+    /// let (values, errors) = logs.par_try_map(|line| line.as_str().parse::<u32>());
+    /// assert_eq!(values.as_slice(), &[Some(12), None, Some(7)]);
+    /// assert_eq!(errors.iter().map(|(idx, _)| *idx).collect::<Vec<_>>(), [1]);
+    /// ```
+    pub fn par_try_map<F, T, E>(&self, f: F) -> (ArcSlice<Option<T>>, Vec<(usize, E)>)
+    where
+        T: Send,
+        E: Send,
+        F: Fn(&Line) -> Result<T, E> + Sync,
+    {
+        let f = &f;
+        let mut results = self.par_map(move |line| Some(f(&line)));
+        let results = results
+            .get_mut()
+            .expect("par_map returns an unshared column");
+        let mut errors = Vec::new();
+        let values = results
+            .iter_mut()
+            .enumerate()
+            .map(|(idx, result)| match result.take() {
+                Some(Ok(value)) => Some(value),
+                Some(Err(err)) => {
+                    errors.push((idx, err));
+                    None
+                }
+                None => unreachable!("each result is taken once"),
+            })
+            .collect_vec();
+        (values.into(), errors)
+    }
+
+    /// Calls `f` with the original index and the line of every line, in
+    /// parallel.
+    ///
+    /// Unlike [`Buffer::par_map`], no result is collected. The index is the line
+    /// number in the content the buffer was created from, as returned by
+    /// [`Buffer::original_index`], so it is stable across `slice` and
+    /// `select`. Lines are split in chunks as in `par_map`, and `f` may be
+    /// called from several threads at once.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// let logs = Buffer::new("INFO a\nERROR b\nERROR c".to_string());
+    /// let errors = AtomicUsize::new(0);
+    /// logs.par_for_each(|_, line| {
+    ///     if line.as_str().starts_with("ERROR") {
+    ///         errors.fetch_add(1, Ordering::Relaxed);
+    ///     }
+    /// });
+    /// assert_eq!(errors.into_inner(), 2);
+    /// ```
+    pub fn par_for_each<F>(&self, f: F)
+    where
+        F: Fn(usize, &Line) + Sync,
+    {
+        let original_index = |idx| {
+            self.original_index(idx)
+                .expect("index is within the buffer")
+        };
+        #[cfg(feature = "no-threads")]
+        self.iter()
+            .enumerate()
+            .for_each(|(idx, line)| f(original_index(idx), &line));
+        #[cfg(not(feature = "no-threads"))]
+        std::thread::scope(|scope| {
+            let (f, original_index) = (&f, &original_index);
+            chunk_ranges(self.len(), parallelism())
+                .map(|rng| {
+                    scope.spawn(move || {
+                        for (idx, line) in rng.clone().zip(self.slice(rng)) {
+                            f(original_index(idx), &line);
+                        }
+                    })
+                })
+                .collect_vec()
+                .into_iter()
+                .for_each(|hndl| hndl.join().unwrap_or_else(|err| panic::resume_unwind(err)))
+        });
+    }
+}
+
+#[cfg(feature = "polars")]
+impl Buffer {
+    /// Returns a `DataFrame` with a single string column `name` holding the
+    /// visible lines, in order.
+    ///
+    /// The lines are copied straight into the column's buffers, without an
+    /// intermediate `String` per line.
+    ///
+    /// ```
+    /// use analogz::containers::Buffer;
+    ///
+    /// let logs = Buffer::new("INFO a\nERROR b\nINFO c".to_string());
+    /// let df = logs.slice(1..).to_string_column("line");
+    /// assert_eq!(df.shape(), (2, 1));
+    /// assert_eq!(df.column("line").unwrap().str().unwrap().get(0), Some("ERROR b"));
+    /// ```
+    pub fn to_string_column(&self, name: &str) -> DataFrame {
+        let lines =
+            StringChunked::from_iter_values(name.into(), self.iter().map(Line::into_arc_str));
+        DataFrame::new(vec![lines.into_column()]).expect("a single column is a valid frame")
+    }
+}
+
+/// Collects lines into a `Buffer` with [`Buffer::from_lines`]: lines are
+/// joined with `\n` and no trailing newline is added.
+///
+/// ```
+/// use analogz::containers::{ArcStr, Buffer};
+///
+/// let logs: Buffer = ["a", "b"].into_iter().map(ArcStr::from).collect();
+/// assert_eq!(logs.to_string(), "a\nb");
+/// ```
+impl FromIterator<ArcStr> for Buffer {
+    fn from_iter<T: IntoIterator<Item = ArcStr>>(iter: T) -> Self {
+        Buffer::from_lines(iter)
+    }
+}
+
+impl FromIterator<String> for Buffer {
+    fn from_iter<T: IntoIterator<Item = String>>(iter: T) -> Self {
+        Buffer::from_lines(iter)
+    }
+}
+
+/// Splits `0..len` into at most `chunks` contiguous, non-empty ranges of
+/// nearly equal size, one per worker of the parallel operations. Every index
+/// is in exactly one range, and an empty `len` yields no range.
+#[cfg(not(feature = "no-threads"))]
+fn chunk_ranges(len: usize, chunks: usize) -> impl Iterator<Item = Range<usize>> {
+    let chunk_size = len.div_ceil(chunks.max(1)).max(1);
+    (len > 0)
+        .then(|| SteppedRange::new(0, len, chunk_size))
+        .into_iter()
+        .flatten()
+        .map(move |start| start..(start + chunk_size).min(len))
+}
+
+impl Display for Buffer {
+    /// Writes the visible lines separated by `\n`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, line) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            f.write_str(line.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+/// Builder for a `Buffer`, created by [`Buffer::builder`].
+#[derive(Debug, Clone)]
+pub struct BufferBuilder {
+    parallel_threshold: usize,
+    line_termination: LineTermination,
+}
+
+/// An overview of a [`Buffer`], as returned by [`Buffer::summary`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BufferSummary {
+    /// The number of lines, see [`Buffer::len`].
+    pub line_count: usize,
+    /// The number of bytes of the lines, see [`Buffer::byte_len`].
+    pub byte_len: usize,
+    /// The number of distinct lines, see [`Buffer::unique_lines`].
+    pub distinct_lines: usize,
+    /// The mean length of a line in bytes, `0.0` for an empty buffer.
+    pub avg_line_len: f64,
+    /// Whether the lines end with `\r\n`, see [`Buffer::has_crlf`].
+    pub has_crlf: bool,
+}
+
+/// Whether a trailing `\n` is followed by an empty last line.
+///
+/// `Buffer::new` splits on every `\n`, so `"a\nb"` has 2 lines and `"a\nb\n"`
+/// has 3, the last one empty (`IncludeTrailingEmpty`, the default).
+/// `OmitTrailingEmpty` drops that final empty line like `str::lines`, giving 2
+/// lines for both. Either way the empty string is a single empty line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LineTermination {
+    #[default]
+    IncludeTrailingEmpty,
+    OmitTrailingEmpty,
+}
+
+impl BufferBuilder {
+    /// Content shorter than this many bytes is indexed sequentially by default.
+    ///
+    /// Spawning the indexing threads costs about 25 µs. In the `buffer_index`
+    /// bench, the parallel build catches up with the sequential one between 64
+    /// and 128 KiB even on a single core, and more cores only move that point
+    /// lower.
+    pub const DEFAULT_PARALLEL_THRESHOLD: usize = 64 * 1024;
+
+    /// Sets the content length, in bytes, from which the line index is built in
+    /// parallel. `0` always builds in parallel and `usize::MAX` never does.
+    pub fn parallel_threshold(mut self, bytes: usize) -> Self {
+        self.parallel_threshold = bytes;
+        self
+    }
+
+    /// Sets how a trailing newline is handled; see [`LineTermination`].
+    pub fn line_termination(mut self, policy: LineTermination) -> Self {
+        self.line_termination = policy;
+        self
+    }
+
+    pub fn build(&self, content: String) -> Buffer {
+        let buffer = if content.len() < self.parallel_threshold {
+            Buffer::new_sequential(content)
+        } else {
+            Buffer {
+                index: CutIndex::build_par(&content, |c| c == &b'\n'),
+                astr: ArcStr::from(content),
+                select: None,
+            }
+        };
+        match self.line_termination {
+            LineTermination::IncludeTrailingEmpty => buffer,
+            LineTermination::OmitTrailingEmpty => buffer.with_trailing_empty(false),
+        }
+    }
+}
+
+impl Default for BufferBuilder {
+    fn default() -> Self {
+        Self {
+            parallel_threshold: Self::DEFAULT_PARALLEL_THRESHOLD,
+            line_termination: LineTermination::default(),
+        }
     }
 }
 
@@ -298,6 +1678,8 @@ impl IntoIterator for Buffer {
 ///
 /// Each `Line` contains a reference to the original string slice,
 /// as well as the start and end positions within the original buffer.
+/// It shares ownership of the content, so it stays valid after the `Buffer`
+/// it came from is dropped.
 #[derive(Debug, Clone)]
 pub struct Line {
     astr: ArcStr,
@@ -335,6 +1717,604 @@ impl From<Line> for ArcStr {
 mod tests {
     use super::*;
 
+    #[cfg(feature = "polars")]
+    #[test]
+    fn to_string_column_holds_visible_lines() {
+        let buffer = Buffer::new("a\nbb\nccc\ndddd".to_string());
+        let selected = buffer.select([3, 1]).unwrap();
+        for part in [&buffer, &selected] {
+            let df = part.to_string_column("line");
+            assert_eq!(df.height(), part.len());
+            let column = df.column("line").unwrap().str().unwrap();
+            assert!(
+                column
+                    .into_iter()
+                    .zip(part.iter())
+                    .all(|(value, line)| value == Some(line.as_str()))
+            );
+        }
+    }
+
+    #[test]
+    fn prebucket_groups_by_token_count_and_first_word() {
+        let buffer = Buffer::new(
+            [
+                "connect to db1 failed",
+                "connect to db2 failed",
+                "connect to db3",
+                "disconnect from db1 ok",
+                "",
+                "   ",
+            ]
+            .join("\n"),
+        );
+        let buckets = buffer.prebucket();
+        let bucket = |count, first: &str| buckets[&(count, ArcStr::from(first))].as_slice();
+        assert_eq!(buckets.len(), 4);
+        assert_eq!(bucket(4, "connect"), &[0, 1]);
+        assert_eq!(bucket(3, "connect"), &[2]);
+        assert_eq!(bucket(4, "disconnect"), &[3]);
+        assert_eq!(bucket(0, ""), &[4, 5]);
+    }
+
+    #[test]
+    fn par_for_each_visits_every_line_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let buffer = Buffer::new((0..10_000).map(|i| i.to_string()).join("\n"));
+        let visits = (0..buffer.len()).map(|_| AtomicUsize::new(0)).collect_vec();
+        let total = AtomicUsize::new(0);
+        buffer.par_for_each(|idx, line| {
+            assert_eq!(line.as_str(), idx.to_string());
+            visits[idx].fetch_add(1, Ordering::Relaxed);
+            total.fetch_add(1, Ordering::Relaxed);
+        });
+        assert_eq!(total.into_inner(), 10_000);
+        assert!(visits.iter().all(|v| v.load(Ordering::Relaxed) == 1));
+
+        let selected = buffer.slice(1..).select([4, 0]).unwrap();
+        let seen = std::sync::Mutex::new(Vec::new());
+        selected.par_for_each(|idx, line| {
+            assert_eq!(line.as_str(), idx.to_string());
+            seen.lock().unwrap().push(idx);
+        });
+        assert_eq!(
+            seen.into_inner()
+                .unwrap()
+                .into_iter()
+                .sorted()
+                .collect_vec(),
+            [1, 5]
+        );
+    }
+
+    #[test]
+    fn compact_copies_lines_into_new_backing() {
+        let buffer = Buffer::new("a\nbb\nccc\ndddd\n".to_string());
+        for part in [
+            buffer.select([3, 0, 2]).unwrap(),
+            buffer.slice(1..3),
+            buffer.clone(),
+            buffer.slice(2..2),
+        ] {
+            let compacted = part.compact();
+            assert_eq!(compacted.len(), part.len());
+            assert_eq!(compacted.join("\n"), part.join("\n"));
+            assert!(!std::sync::Arc::ptr_eq(
+                compacted.astr.as_arc_str(),
+                buffer.astr.as_arc_str()
+            ));
+            assert_eq!(compacted.original_index(0), (!part.is_empty()).then_some(0));
+        }
+        assert_eq!(std::sync::Arc::strong_count(buffer.astr.as_arc_str()), 1);
+    }
+
+    #[test]
+    fn summary_of_known_buffer() {
+        let buffer = Buffer::new("GET /\r\nPOST /a\r\nGET /\r\n".to_string());
+        assert_eq!(
+            buffer.summary(),
+            BufferSummary {
+                line_count: 4,
+                byte_len: 20,
+                distinct_lines: 3,
+                avg_line_len: 5.0,
+                has_crlf: true,
+            }
+        );
+
+        let summary = Buffer::new("ok\nfine".to_string()).summary();
+        assert!(!summary.has_crlf);
+        assert_eq!(summary.avg_line_len, 3.0);
+        assert_eq!(buffer.slice(4..).summary().avg_line_len, 0.0);
+    }
+
+    #[test]
+    fn has_crlf_needs_a_newline_after_the_carriage_return() {
+        assert!(!Buffer::new("a\r".to_string()).has_crlf());
+        assert!(!Buffer::new("a\nb\r".to_string()).has_crlf());
+        assert!(!Buffer::new("a\rb\nc".to_string()).has_crlf());
+        assert!(Buffer::new("a\r\nb".to_string()).has_crlf());
+
+        let buffer = Buffer::new("a\nb\r\nc\nd\r".to_string());
+        assert!(buffer.has_crlf());
+        assert!(buffer.slice(1..2).has_crlf());
+        assert!(!buffer.select([0, 2, 3]).unwrap().has_crlf());
+    }
+
+    #[test]
+    fn lines_in_byte_range_yields_whole_lines() {
+        let buffer = Buffer::new("alpha\nbeta\ngamma\ndelta".to_string());
+        let lines = |buffer: &Buffer, bytes: Range<usize>| {
+            buffer
+                .lines_in_byte_range(bytes)
+                .map(|(idx, line)| (idx, line.to_string()))
+                .collect_vec()
+        };
+        assert_eq!(
+            lines(&buffer, 8..13),
+            [(1, "beta".to_string()), (2, "gamma".to_string())]
+        );
+        assert_eq!(lines(&buffer, 5..6), [(0, "alpha".to_string())]);
+        assert_eq!(lines(&buffer, 20..500), [(3, "delta".to_string())]);
+        assert!(lines(&buffer, 100..200).is_empty());
+        assert!(lines(&buffer, 8..8).is_empty());
+
+        let tail = buffer.slice(2..);
+        assert_eq!(lines(&tail, 0..12), [(0, "gamma".to_string())]);
+
+        let selected = buffer.select([3, 1]).unwrap();
+        assert_eq!(
+            lines(&selected, 0..100),
+            [(0, "delta".to_string()), (1, "beta".to_string())]
+        );
+        assert_eq!(lines(&selected, 0..8), [(1, "beta".to_string())]);
+
+        for (content, end) in [("alpha\nbeta", 10), ("alpha\nbeta\n", 11)] {
+            let buffer = Buffer::new(content.to_string());
+            let selected = buffer.select(0..buffer.len()).unwrap();
+            for buffer in [&buffer, &selected] {
+                assert_eq!(lines(buffer, end - 1..end + 5), [(1, "beta".to_string())]);
+                assert!(lines(buffer, end..end + 5).is_empty(), "{content:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn line_hashes_are_stable_and_normalize() {
+        let buffer = Buffer::new("  A \na\nA\na b\na  b\nb a".to_string());
+        let exact = buffer.line_hashes(false);
+        assert_eq!(exact.len(), 6);
+        assert_eq!(exact[1], {
+            let mut hasher = Fnv1a::default();
+            hasher.write(b"a");
+            hasher.finish()
+        });
+        assert_ne!(exact[0], exact[1]);
+        assert_ne!(exact[1], exact[2]);
+
+        let normalized = buffer.line_hashes(true);
+        assert_eq!(normalized[0], normalized[1]);
+        assert_eq!(normalized[1], normalized[2]);
+        assert_eq!(normalized[1], exact[1]);
+        assert_eq!(normalized[3], normalized[4]);
+        assert_ne!(normalized[4], normalized[5]);
+    }
+
+    #[test]
+    fn map_ranges_pairs_values_with_line_spans() {
+        let content = "alpha\n\nbeta gamma\nz".to_string();
+        let buffer = Buffer::new(content.clone());
+        let mapped = buffer.map_ranges(|line| line.as_str().to_uppercase());
+        assert_eq!(mapped.len(), buffer.len());
+        for ((range, value), line) in mapped.iter().zip(buffer.iter()) {
+            assert_eq!(range.clone(), line.start()..line.end());
+            assert_eq!(*value, content[range.clone()].to_uppercase());
+        }
+
+        let tail = buffer.slice(2..);
+        let mapped = tail.map_ranges(|line| line.len());
+        assert_eq!(mapped.as_slice(), &[(7..17, 10), (18..19, 1)]);
+    }
+
+    #[test]
+    fn line_windows_sizes_and_tails() {
+        let logs = Buffer::new("a\nb\nc\nd\ne".to_string());
+        let groups = |size, step, partial| {
+            logs.line_windows(size, step, partial)
+                .map(|w| w.join(""))
+                .collect_vec()
+        };
+        assert_eq!(groups(3, 1, false), ["abc", "bcd", "cde"]);
+        assert_eq!(groups(3, 1, true), ["abc", "bcd", "cde"]);
+        assert_eq!(groups(3, 2, false), ["abc", "cde"]);
+        assert_eq!(groups(3, 3, false), ["abc"]);
+        assert_eq!(groups(3, 3, true), ["abc", "de"]);
+        assert_eq!(groups(2, 3, true), ["ab", "de"]);
+        assert_eq!(groups(4, 2, true), ["abcd", "cde"]);
+        assert_eq!(groups(9, 1, false), Vec::<String>::new());
+        assert_eq!(groups(9, 1, true), ["abcde"]);
+        assert_eq!(logs.slice(0..0).line_windows(2, 1, true).count(), 0);
+
+        let window = logs.line_windows(2, 2, false).nth(1).unwrap();
+        assert_eq!(window.original_index(0), Some(2));
+    }
+
+    #[test]
+    fn original_index_through_slices_and_selects() {
+        let logs = Buffer::new((0..10).map(|i| format!("l{i}")).join("\n"));
+        let check = |buffer: &Buffer| {
+            for (idx, line) in buffer.iter().enumerate() {
+                let original = buffer.original_index(idx).unwrap();
+                assert_eq!(line.as_str(), format!("l{original}"));
+            }
+            assert_eq!(buffer.original_index(buffer.len()), None);
+        };
+
+        let filtered = logs.slice(1..).select([0, 2]).unwrap();
+        assert_eq!(filtered.original_index(1), Some(3));
+        check(&filtered);
+        check(&logs.slice(2..8).slice(3..));
+        check(&logs.slice(2..9).select([6, 0, 3, 4]).unwrap().slice(1..3));
+        check(&logs.select([9, 1, 5]).unwrap().select([2, 0]).unwrap());
+        check(&logs.slice(4..4));
+    }
+
+    #[test]
+    fn line_termination_policies() {
+        use LineTermination::*;
+        let lines = |content: &str, policy| {
+            let logs = Buffer::new_with(content.to_string(), policy);
+            logs.iter().map(|l| l.as_str().to_string()).collect_vec()
+        };
+        assert_eq!(lines("a\nb\n", IncludeTrailingEmpty), ["a", "b", ""]);
+        assert_eq!(lines("a\nb\n", OmitTrailingEmpty), ["a", "b"]);
+        assert_eq!(lines("a\nb", OmitTrailingEmpty), ["a", "b"]);
+        assert_eq!(lines("a\n\n", OmitTrailingEmpty), ["a", ""]);
+        assert_eq!(lines("", OmitTrailingEmpty), [""]);
+        for content in ["a\nb\n", "a\n\n", "x", "\n"] {
+            assert_eq!(
+                lines(content, OmitTrailingEmpty),
+                content.lines().collect_vec()
+            );
+        }
+
+        let parallel = Buffer::builder()
+            .parallel_threshold(0)
+            .line_termination(OmitTrailingEmpty)
+            .build("a\nb\n".to_string());
+        assert_eq!(parallel.len(), 2);
+    }
+
+    #[test]
+    fn grep_all_requires_every_regex() {
+        let logs = Buffer::new(
+            [
+                "user=alice status=500 path=/a",
+                "user=alice status=200 path=/a",
+                "user=bob status=500 path=/a",
+                "path=/a status=500 user=alice",
+            ]
+            .join("\n"),
+        );
+        let res = ["user=alice", "status=500", "path=/a"].map(|re| Regex::new(re).unwrap());
+        assert_eq!(
+            logs.grep_all(&res).join("\n"),
+            "user=alice status=500 path=/a\npath=/a status=500 user=alice"
+        );
+        assert_eq!(logs.grep_all(&res[..1]).len(), 3);
+        assert_eq!(logs.grep_all(&[]).len(), 4);
+    }
+
+    #[test]
+    fn group_by_template_top_templates() {
+        let logs = Buffer::new(
+            [
+                "conn 1 open",
+                "GET /a 200",
+                "conn 2 open",
+                "GET /a 404",
+                "disk full",
+                "conn 3 open",
+                "GET /a 200",
+                "GET /b 200",
+                "conn 10 open",
+            ]
+            .join("\n"),
+        );
+        let report = logs.group_by_template();
+        assert_eq!(report.len(), 4);
+        assert_eq!(report.groups()[1].lines, [1, 3, 6]);
+
+        let top = report.top(2);
+        assert_eq!(top.len(), 2);
+        assert_eq!(
+            (top[0].pattern.as_str(), top[0].count),
+            ("conn <1-10> open", 4)
+        );
+        assert_eq!(top[0].example, "conn 1 open");
+        assert_eq!(
+            (top[1].pattern.as_str(), top[1].count),
+            ("GET /a <200-404>", 3)
+        );
+
+        let counts = report.top(10).iter().map(|s| s.count).collect_vec();
+        assert_eq!(counts, [4, 3, 1, 1]);
+        assert_eq!(report.top(10)[2].example, "disk full");
+        assert_eq!(report.top(10)[3].example, "GET /b 200");
+    }
+
+    #[test]
+    fn enumerate_matches_yields_captures_as_line_slices() {
+        let logs = Buffer::new("GET /a 200\nnoise\nPOST /b 500\nGET /c\nPUT /d 201".to_string());
+        let re = Regex::new(r"^(GET|POST) (\S+)(?: (\d+))?$").unwrap();
+        let matches = logs.enumerate_matches(&re).collect_vec();
+        assert_eq!(
+            matches.iter().map(|(idx, _, _)| *idx).collect_vec(),
+            [0, 2, 3]
+        );
+
+        let (_, line, captures) = &matches[1];
+        assert_eq!(line.as_str(), "POST /b 500");
+        let captures = captures
+            .iter()
+            .map(|c| c.as_ref().map(|c| c.as_str()))
+            .collect_vec();
+        assert_eq!(captures, [Some("POST"), Some("/b"), Some("500")]);
+
+        let (_, line, captures) = &matches[2];
+        let path = captures[1].as_ref().unwrap();
+        assert_eq!(
+            (path.start(), path.end()),
+            (line.start() + 4, line.start() + 6)
+        );
+        assert!(captures[2].is_none());
+    }
+
+    #[test]
+    fn from_bytes_lossy_replaces_invalid_sequences() {
+        let mut bytes = b"GET /a 200\n".to_vec();
+        bytes.extend([0xFF, 0xFE, b' ', b'x', b'\n', 0xC3]);
+        let logs = Buffer::from_bytes_lossy(bytes);
+        assert_eq!(logs.len(), 3);
+        assert_eq!(logs.get(0).unwrap().as_str(), "GET /a 200");
+        assert_eq!(logs.get(1).unwrap().as_str(), "\u{FFFD}\u{FFFD} x");
+        assert_eq!(logs.get(2).unwrap().as_str(), "\u{FFFD}");
+
+        let valid = Buffer::from_bytes_lossy("héllo\nwörld".as_bytes().to_vec());
+        assert_eq!(valid.as_str(), "héllo\nwörld");
+    }
+
+    fn temp_file(name: &str, bytes: &[u8]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("analogz-{}-{name}", std::process::id()));
+        std::fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn from_file_reads_lines() {
+        let path = temp_file("plain.log", b"INFO start\nERROR boom");
+        let buffer = Buffer::from_file(&path).unwrap();
+        assert_eq!(buffer.join(","), "INFO start,ERROR boom");
+
+        std::fs::write(&path, b"bad \xF0\x90 byte").unwrap();
+        let err = Buffer::from_file(&path).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(feature = "flate2")]
+    #[test]
+    fn from_file_decompresses_gzip() {
+        use flate2::{Compression, write::GzEncoder};
+        use std::io::Write;
+
+        let content = "2024-01-15 INFO start\n2024-01-15 WARN slow\n2024-01-15 ERROR boom";
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(content.as_bytes()).unwrap();
+        let path = temp_file("archived.log", &encoder.finish().unwrap());
+
+        let buffer = Buffer::from_file(&path).unwrap();
+        let plain = Buffer::new(content.to_string());
+        assert_eq!(buffer.len(), plain.len());
+        assert!(
+            buffer
+                .iter()
+                .zip(plain.iter())
+                .all(|(a, b)| a.as_str() == b.as_str())
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn select_slice_equals_select() {
+        let logs = Buffer::new("a\nb\nc\nd\ne".to_string());
+        let indices = ArcSlice::new(vec![9, 4, 1, 3, 1]).slice(1..);
+        let expected = logs.select(indices.iter().copied()).unwrap();
+        assert_eq!(
+            logs.select_slice(&indices).unwrap().join(","),
+            expected.join(",")
+        );
+
+        let selected = logs.select([4, 3, 2, 0]).unwrap();
+        let indices = ArcSlice::new(vec![3, 0]);
+        assert_eq!(selected.select_slice(&indices).unwrap().join(","), "a,e");
+
+        assert!(logs.select_slice(&ArcSlice::new(vec![0, 5])).is_err());
+        assert!(selected.select_slice(&ArcSlice::new(vec![4])).is_err());
+    }
+
+    #[test]
+    fn collect_lines_into_buffer() {
+        let source = ArcStr::from("x a b c");
+        let logs: Buffer = source.split(' ').skip(1).collect();
+        assert_eq!(logs.len(), 3);
+        assert_eq!(logs.get(2).unwrap().as_str(), "c");
+
+        let logs: Buffer = (1..=3).map(|i| format!("line {i}")).collect();
+        assert_eq!(logs.as_str(), "line 1\nline 2\nline 3");
+    }
+
+    #[test]
+    fn line_spans_with_terminator_are_contiguous() {
+        for content in ["a\nbb\r\n\nccc", "a\nb\n", "", "\n\n", "single"] {
+            let logs = Buffer::new(content.to_string());
+            let spans = (0..logs.len())
+                .map(|idx| logs.line_span_with_terminator(idx).unwrap())
+                .collect_vec();
+            assert_eq!(spans.first().unwrap().start, 0);
+            assert_eq!(spans.last().unwrap().end, content.len());
+            for (prev, next) in spans.iter().tuple_windows() {
+                assert_eq!(prev.end, next.start, "{content:?}");
+                assert_eq!(&content[prev.end - 1..prev.end], "\n");
+            }
+        }
+
+        let logs = Buffer::new("x\ny\nz".to_string());
+        let selected = logs.select([2, 0]).unwrap();
+        assert_eq!(selected.line_span_with_terminator(0), Some(4..5));
+        assert_eq!(selected.line_span_with_terminator(1), Some(0..2));
+    }
+
+    #[cfg(not(feature = "no-threads"))]
+    #[test]
+    fn chunk_ranges_cover_every_index_once() {
+        let mut rng = SplitMix64::new(7);
+        let lengths = (0..70).chain((0..50).map(|_| rng.below(100_000)));
+        for len in lengths {
+            for chunks in [0, 1, 2, 3, 4, 7, 8, 16, 33, 64, 128] {
+                let ranges = chunk_ranges(len, chunks).collect_vec();
+                assert!(ranges.len() <= chunks.max(1), "len {len}, chunks {chunks}");
+                assert!(ranges.iter().all(|r| !r.is_empty()));
+                let mut next = 0;
+                for r in &ranges {
+                    assert_eq!(r.start, next, "len {len}, chunks {chunks}");
+                    next = r.end;
+                }
+                assert_eq!(next, len, "len {len}, chunks {chunks}");
+            }
+        }
+    }
+
+    #[test]
+    fn par_maps_handle_empty_and_short_buffers() {
+        let logs = Buffer::new((0..37).join("\n"));
+        for len in 0..=logs.len() {
+            let part = logs.slice(0..len);
+            let expected = (0..len).collect_vec();
+            let index = |line: Line| line.as_str().parse::<usize>().unwrap();
+            assert_eq!(part.par_map(index).as_slice(), expected);
+            assert_eq!(
+                part.try_par_map(|line| Ok::<_, ()>(index(line)))
+                    .unwrap()
+                    .as_slice(),
+                expected
+            );
+            let (values, errors) = part.par_try_map(|line| Ok::<_, ()>(index(line.clone())));
+            assert_eq!(values.iter().flatten().copied().collect_vec(), expected);
+            assert!(errors.is_empty());
+        }
+    }
+
+    #[test]
+    fn unique_lines_keeps_first_occurrences_in_order() {
+        let logs = Buffer::new("start\nping\nerr\nping\n\nerr\nstop\n\nping".to_string());
+        let unique = logs.unique_lines();
+        assert_eq!(unique.join(","), "start,ping,err,,stop");
+        assert_eq!(unique.len(), 5);
+        assert_eq!(unique.unique_lines().join(","), unique.join(","));
+        assert_eq!(logs.slice(1..4).unique_lines().join(","), "ping,err");
+    }
+
+    #[test]
+    fn par_try_map_collects_values_and_errors() {
+        let content = (0..300)
+            .map(|i| {
+                if i % 50 == 7 {
+                    format!("bad{i}")
+                } else {
+                    i.to_string()
+                }
+            })
+            .join("\n");
+        let logs = Buffer::new(content);
+        let (values, errors) = logs.par_try_map(|line| line.as_str().parse::<usize>());
+
+        assert_eq!(values.len(), 300);
+        assert_eq!(values[6], Some(6));
+        assert_eq!(values[7], None);
+        assert_eq!(values.iter().flatten().count(), 294);
+        assert_eq!(
+            errors.iter().map(|(idx, _)| *idx).collect_vec(),
+            [7, 57, 107, 157, 207, 257]
+        );
+
+        let (values, errors) = logs
+            .slice(0..3)
+            .par_try_map(|line| line.as_str().parse::<u8>());
+        assert_eq!(values.as_slice(), &[Some(0), Some(1), Some(2)]);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn grep_ci_selects_all_case_variants() {
+        let logs = Buffer::new("ERROR a\nINFO b\nerror c\nWARN Error d\nerr e".to_string());
+        let matched = logs.grep_ci("error");
+        assert_eq!(matched.join(","), "ERROR a,error c,WARN Error d");
+        assert_eq!(matched.grep_ci("WARN").join(","), "WARN Error d");
+        assert!(logs.grep_ci("").is_empty());
+    }
+
+    #[test]
+    fn slice_accepts_any_range_and_clamps() {
+        let logs = Buffer::new("a\nb\nc\nd".to_string());
+        assert_eq!(logs.slice(0..).join(","), "a,b,c,d");
+        assert_eq!(logs.slice(..).join(","), "a,b,c,d");
+        assert_eq!(logs.slice(2..).join(","), "c,d");
+        assert_eq!(logs.slice(..=1).join(","), "a,b");
+        assert_eq!(logs.slice(1..usize::MAX).join(","), "b,c,d");
+        assert_eq!(logs.slice(1..=usize::MAX).join(","), "b,c,d");
+        assert!(logs.slice(10..).is_empty());
+
+        let selected = logs.select([0, 2, 3]).unwrap();
+        assert_eq!(selected.slice(1..usize::MAX).join(","), "c,d");
+        assert!(selected.slice(5..).is_empty());
+    }
+
+    #[test]
+    fn from_lines_matches_joined_new() {
+        let logs = Buffer::from_lines(["a", "b"]);
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs.get(1).unwrap().as_str(), "b");
+
+        let cases: [&[&str]; 5] = [&[], &[""], &["", ""], &["x", "", "y\nz"], &["end\n"]];
+        for lines in cases {
+            let from_lines = Buffer::from_lines(lines);
+            let joined = Buffer::new(lines.join("\n"));
+            assert_eq!(from_lines.as_str(), joined.as_str());
+            assert_eq!(
+                from_lines.byte_ranges().collect_vec(),
+                joined.byte_ranges().collect_vec()
+            );
+        }
+    }
+
+    #[test]
+    fn body_skips_header_and_footer() {
+        let logs = Buffer::new("header\nl1\nl2\nl3\nfooter".to_string());
+        let body = logs.body(1, 1);
+        assert_eq!(body.len(), 3);
+        assert_eq!(body.get(0).unwrap().as_str(), "l1");
+        assert_eq!(body.get(2).unwrap().as_str(), "l3");
+
+        assert!(logs.body(5, 0).is_empty());
+        assert!(logs.body(2, 4).is_empty());
+        assert!(logs.body(10, 10).is_empty());
+
+        let selected = logs.select([0, 2, 4]).unwrap();
+        assert_eq!(selected.body(1, 1).join(""), "l2");
+    }
+
     #[test]
     fn test_empty_buffer() {
         let buffer = Buffer::new(String::new());
@@ -390,6 +2370,18 @@ mod tests {
         assert_eq!(lines, vec!["line 1", "line 2", "line 3"]);
     }
 
+    #[test]
+    fn lines_outlive_their_buffer() {
+        let lines: Vec<Line> = {
+            let buffer = Buffer::new("first\nsecond\nthird".to_string());
+            buffer.iter().filter(|l| l.as_str() != "second").collect()
+        };
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].as_str(), "first");
+        assert_eq!((lines[1].start(), lines[1].end()), (13, 18));
+        assert_eq!(lines[1].clone().into_arc_str(), "third");
+    }
+
     #[test]
     fn test_line_as_ref() {
         let content = "test line".to_string();
@@ -412,6 +2404,37 @@ mod tests {
         assert_eq!(lines, vec!["line 1", "line 2", ""]);
     }
 
+    #[test]
+    fn test_trailing_empty_policies() {
+        let buffer = Buffer::new("a\nb\n".to_string());
+
+        let kept = buffer.clone().with_trailing_empty(true);
+        assert_eq!(kept.len(), 3);
+        assert_eq!(
+            kept.iter_with_terminators().collect_vec(),
+            ["a\n", "b\n", ""]
+        );
+
+        let dropped = buffer.with_trailing_empty(false);
+        assert_eq!(dropped.len(), 2);
+        assert_eq!(
+            dropped.iter_with_terminators().collect_vec(),
+            ["a\n", "b\n"]
+        );
+        assert_eq!(dropped.to_string(), "a\nb");
+    }
+
+    #[test]
+    fn test_trailing_empty_keeps_other_empty_lines() {
+        let empty = Buffer::new(String::new()).with_trailing_empty(false);
+        assert_eq!(empty.len(), 1);
+
+        let buffer = Buffer::new("a\n\nb".to_string()).with_trailing_empty(false);
+        assert_eq!(buffer.len(), 3);
+        let middle = buffer.slice(0..2).with_trailing_empty(false);
+        assert_eq!(middle.len(), 2);
+    }
+
     #[test]
     fn test_consecutive_newlines() {
         let content = "line 1\n\nline 3".to_string();
@@ -562,6 +2585,44 @@ mod tests {
         assert_eq!(slice.last().unwrap().as_deref(), Some(""));
     }
 
+    #[test]
+    fn parallel_maps_match_map() {
+        // Holds with and without the `no-threads` feature.
+        let buffer = Buffer::new((0..777).map(|i| format!("Line {i}")).join("\n"));
+        let expected = buffer.map(|line| line.len());
+        assert_eq!(
+            buffer.par_map(|line| line.len()).as_slice(),
+            expected.as_slice()
+        );
+        assert_eq!(
+            buffer
+                .par_map_with_progress(|line| line.len(), |_, _| {})
+                .as_slice(),
+            expected.as_slice()
+        );
+        let tried = buffer.try_par_map(|line| Ok::<_, ()>(line.len()));
+        assert_eq!(tried.unwrap().as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn par_map_places_results_with_uneven_chunks() {
+        // 10 lines over 3 threads gives chunks of 4, 4 and 2; 11 lines over 4
+        // threads gives 3, 3, 3 and 2. Every line must land at its own index.
+        for (lines, threads) in [(10, 3), (11, 4), (7, 8), (1, 3), (13, 5)] {
+            let buffer = Buffer::new((0..lines).map(|i| i.to_string()).join("\n"));
+            let mapped = buffer.par_map_with_threads(
+                threads,
+                |line| line.as_str().parse::<usize>().unwrap(),
+                |_, _| {},
+            );
+            let tried =
+                buffer.try_par_map_with_threads(threads, |line| line.as_str().parse::<usize>());
+
+            assert_eq!(mapped.as_slice(), (0..lines).collect_vec());
+            assert_eq!(tried.unwrap().as_slice(), (0..lines).collect_vec());
+        }
+    }
+
     #[test]
     fn map_retains_none_entries_without_dropping() {
         let content = "a\nb\nc\nd\ne\n".to_string();
@@ -616,6 +2677,268 @@ mod tests {
         assert_eq!(slice[n], Some(0));
     }
 
+    #[test]
+    fn par_map_single_thread_matches_multi_thread() {
+        let content = (0..1001).map(|i| format!("Line {i}")).join("\n");
+        let buffer = Buffer::new(content);
+
+        let single = buffer.par_map_with_threads(1, |line| line.as_str().to_string(), |_, _| {});
+        assert_eq!(single.len(), buffer.len());
+        for threads in [2, 3, 8, 64] {
+            let multi =
+                buffer.par_map_with_threads(threads, |line| line.as_str().to_string(), |_, _| {});
+            assert_eq!(single.as_slice(), multi.as_slice(), "threads {threads}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "bad line")]
+    fn par_map_propagates_panics() {
+        let content = (0..1000).map(|i| format!("Line {i}")).join("\n");
+        let buffer = Buffer::new(content);
+        buffer.par_map(|line| {
+            if line.as_str() == "Line 777" {
+                panic!("bad line");
+            }
+            line.len()
+        });
+    }
+
+    #[test]
+    fn par_map_with_progress_reports_every_line() {
+        use std::sync::Mutex;
+
+        let buffer = Buffer::new((0..1000).map(|i| format!("Line {i}")).join("\n"));
+        let calls = Mutex::new(Vec::new());
+        let mapped = buffer.par_map_with_progress(
+            |line| line.len(),
+            |done, total| calls.lock().unwrap().push((done, total)),
+        );
+        assert_eq!(mapped.len(), 1000);
+
+        let mut calls = calls.into_inner().unwrap();
+        assert!(calls.iter().all(|(_, total)| *total == 1000));
+        calls.sort();
+        assert_eq!(calls.last(), Some(&(1000, 1000)));
+    }
+
+    #[test]
+    fn try_par_map_returns_first_error_in_line_order() {
+        let content = (0..1000).map(|i| format!("{i}")).join("\n");
+        let buffer = Buffer::new(content);
+
+        let parsed = buffer.try_par_map(|line| line.as_str().parse::<usize>());
+        assert_eq!(parsed.unwrap().as_slice(), (0..1000).collect_vec());
+
+        let result = buffer.try_par_map(|line| {
+            let n = line.as_str().parse::<usize>().unwrap();
+            if n % 300 == 299 { Err(n) } else { Ok(n) }
+        });
+        assert_eq!(result.unwrap_err(), 299);
+    }
+
+    #[test]
+    fn classify_allows_multiple_labels_per_line() {
+        let buffer = Buffer::new(
+            "login ok\nuser auth failed on db replica\nconnection reset\ndb slow".to_string(),
+        );
+        let labels = [
+            ("auth".to_string(), Regex::new(r"auth|login").unwrap()),
+            ("db".to_string(), Regex::new(r"\bdb\b").unwrap()),
+            ("net".to_string(), Regex::new(r"connection|socket").unwrap()),
+        ];
+        let columns = buffer.classify(&labels);
+        assert_eq!(columns.len(), 3);
+        assert_eq!(columns[0].as_slice(), [true, true, false, false]);
+        assert_eq!(columns[1].as_slice(), [false, true, false, true]);
+        assert_eq!(columns[2].as_slice(), [false, false, true, false]);
+    }
+
+    #[test]
+    fn classify_honors_regex_builder_flags() {
+        let buffer = Buffer::new(
+            "ERROR disk
+error net
+INFO ok"
+                .to_string(),
+        );
+        let label_re = regex::RegexBuilder::new("error")
+            .case_insensitive(true)
+            .build()
+            .unwrap();
+        let columns = buffer.classify(&[("error".to_string(), label_re.clone())]);
+        let expected = buffer
+            .iter()
+            .map(|line| label_re.is_match(line.as_str()))
+            .collect_vec();
+        assert_eq!(columns[0].as_slice(), expected);
+        assert_eq!(columns[0].as_slice(), [true, true, false]);
+    }
+
+    #[test]
+    fn parse_records_into_access_logs() {
+        #[derive(Debug, PartialEq)]
+        struct AccessLog {
+            ip: String,
+            method: String,
+            path: String,
+            status: u16,
+        }
+
+        impl FromLine for AccessLog {
+            fn from_line(line: &ArcStr) -> Option<Self> {
+                let mut fields = line.fields(' ', Some('"')).map(|(_, f)| f);
+                let ip = fields.next()?.as_str().to_string();
+                let request = fields.next()?.trim_matches('"');
+                let status = fields.next()?.as_str().parse().ok()?;
+                let (method, path) = request.as_str().split_once(' ')?;
+                Some(AccessLog {
+                    ip,
+                    method: method.to_string(),
+                    path: path.to_string(),
+                    status,
+                })
+            }
+        }
+
+        let buffer = Buffer::new(
+            [
+                r#"10.0.0.1 "GET /index.html" 200"#,
+                "-- log rotated --",
+                r#"192.168.1.7 "POST /api/login" 401"#,
+            ]
+            .join("\n"),
+        );
+        let records = buffer.parse_records::<AccessLog>();
+        assert_eq!(records.len(), 3);
+        assert_eq!(
+            records[0],
+            Some(AccessLog {
+                ip: "10.0.0.1".into(),
+                method: "GET".into(),
+                path: "/index.html".into(),
+                status: 200,
+            })
+        );
+        assert_eq!(records[1], None);
+        assert_eq!(records[2].as_ref().map(|r| r.status), Some(401));
+    }
+
+    #[test]
+    fn find_first_returns_earliest_match() {
+        let buffer = Buffer::new("a ok\nb fail\nc ok\nd fail\ne".to_string());
+        let fail = Regex::new("fail").unwrap();
+
+        let (idx, line) = buffer.find_first(&fail).unwrap();
+        assert_eq!((idx, line.as_str()), (1, "b fail"));
+
+        let selected = buffer.select([4, 3, 1]).unwrap();
+        let (idx, line) = selected.find_first(&fail).unwrap();
+        assert_eq!((idx, line.as_str()), (1, "d fail"));
+
+        assert!(buffer.find_first(&Regex::new("missing").unwrap()).is_none());
+        assert!(buffer.slice(2..3).find_first(&fail).is_none());
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn match_ranges_are_relative_to_lines() {
+        let buffer =
+            Buffer::new("no match\nuser=bob ip=1.2.3.4 ip=5.6.7.8\nip=9.9.9.9".to_string());
+        let re = Regex::new(r"ip=[\d.]+").unwrap();
+
+        let ranges = buffer.match_ranges(&re).collect_vec();
+        assert_eq!(ranges, vec![(1, vec![9..19, 20..30]), (2, vec![0..10])]);
+
+        let line = buffer.get(1).unwrap();
+        let matched = ranges[0]
+            .1
+            .iter()
+            .map(|r| &line.as_str()[r.clone()])
+            .collect_vec();
+        assert_eq!(matched, ["ip=1.2.3.4", "ip=5.6.7.8"]);
+    }
+
+    #[test]
+    fn diff_reports_inserted_and_deleted_lines() {
+        use DiffOp::*;
+
+        let before = Buffer::new("boot\nmount /data\nstart api\nready".to_string());
+        let after = Buffer::new("boot\nstart api\nstart worker\nready".to_string());
+        assert_eq!(
+            before.diff(&after),
+            [Equal(0), Delete(1), Equal(2), Insert(2), Equal(3)]
+        );
+        assert_eq!(
+            before.slice(1..3).diff(&after.select([1]).unwrap()),
+            [Delete(0), Equal(1)]
+        );
+    }
+
+    #[test]
+    fn grep_tokens_matches_whole_tokens() {
+        let buffer = Buffer::new(
+            "errorless log\nan error here\n[error] at start\nERROR upper\nno match".to_string(),
+        );
+        let keywords = HashSet::from([ArcStr::from("error")]);
+
+        let found = buffer.grep_tokens(&keywords);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found.get(0).unwrap().as_str(), "an error here");
+        assert_eq!(found.get(1).unwrap().as_str(), "[error] at start");
+
+        let found_in_selection = buffer.select([0, 2, 4]).unwrap().grep_tokens(&keywords);
+        assert_eq!(found_in_selection.to_string(), "[error] at start");
+        assert!(buffer.grep_tokens(&HashSet::new()).is_empty());
+    }
+
+    #[test]
+    fn test_sample_is_deterministic() {
+        let content = (0..500).map(|i| format!("Line {i}")).join("\n");
+        let buffer = Buffer::new(content);
+
+        let a = buffer.sample(20, 7).iter().map(|l| l.start()).collect_vec();
+        let b = buffer.sample(20, 7).iter().map(|l| l.start()).collect_vec();
+        assert_eq!(a.len(), 20);
+        assert_eq!(a, b);
+        assert!(a.is_sorted());
+    }
+
+    #[test]
+    fn test_sample_larger_than_buffer_returns_all() {
+        let buffer = Buffer::new("line 1\nline 2\nline 3".to_string());
+        let sample = buffer.sample(10, 1);
+        let lines = sample.iter().map(|l| l.as_str().to_string()).collect_vec();
+        assert_eq!(lines, vec!["line 1", "line 2", "line 3"]);
+    }
+
+    #[test]
+    fn test_sample_of_selection() {
+        let buffer = Buffer::new("a\nb\nc\nd\ne".to_string());
+        let selected = buffer.select([1, 3]).unwrap();
+        let sample = selected.sample(5, 3);
+        let lines = sample.iter().map(|l| l.as_str().to_string()).collect_vec();
+        assert_eq!(lines, vec!["b", "d"]);
+    }
+
+    #[test]
+    fn byte_ranges_match_lines() {
+        let buffer = Buffer::new("line 1\n\nline 3\nline 4\n".to_string());
+        for view in [
+            buffer.clone(),
+            buffer.slice(1..4),
+            buffer.select([3, 0, 4]).unwrap(),
+            buffer.slice(1..5).select([2, 0]).unwrap(),
+        ] {
+            let expected = view.iter().map(|l| l.start()..l.end()).collect_vec();
+            assert_eq!(view.byte_ranges().collect_vec(), expected);
+            for (i, rng) in view.byte_ranges().enumerate() {
+                let line = view.get(i).unwrap();
+                assert_eq!(rng, line.start()..line.end());
+            }
+        }
+    }
+
     #[test]
     fn test_select() {
         let content = "line 1\nline 2\nline 3\nline 4\nline 5".to_string();
@@ -639,6 +2962,114 @@ mod tests {
         assert!(buffer.select([0, 5, 6]).is_err())
     }
 
+    #[test]
+    fn test_to_string_of_non_adjacent_selection() {
+        let content = "line 1\nline 2\nline 3\nline 4\nline 5".to_string();
+        let buffer = Buffer::new(content.clone());
+
+        let selected = buffer.select([0, 2, 4]).unwrap();
+        assert_eq!(selected.to_string(), "line 1\nline 3\nline 5");
+        assert_eq!(selected.join("\n"), selected.to_string());
+
+        assert_eq!(buffer.to_string(), content);
+        assert_eq!(buffer.slice(1..3).to_string(), "line 2\nline 3");
+    }
+
+    #[test]
+    fn wrapped_segments_reassemble_lines() {
+        let logs = Buffer::new("héllo wörld\n\nshort\n日本語のログ行です".to_string());
+        let segments = logs.wrapped(4).collect_vec();
+        assert!(segments.iter().all(|(_, seg)| seg.chars().count() <= 4));
+        for (idx, line) in logs.iter().enumerate() {
+            let rebuilt: String = segments
+                .iter()
+                .filter(|(i, _)| *i == idx)
+                .map(|(_, seg)| seg.as_str())
+                .collect();
+            assert_eq!(rebuilt, line.as_str());
+        }
+        assert_eq!(
+            segments
+                .iter()
+                .filter(|(i, _)| *i == 3)
+                .map(|(_, seg)| seg.as_str())
+                .collect_vec(),
+            vec!["日本語の", "ログ行で", "す"]
+        );
+    }
+
+    #[test]
+    fn empty_semantics_are_consistent() {
+        let empty_content = Buffer::new(String::new());
+        assert_eq!(empty_content.len(), 1);
+        assert_eq!(empty_content.iter().map(|l| l.len()).collect_vec(), [0]);
+
+        let buffer = Buffer::new("line 1\nline 2".to_string());
+        for empty in [
+            empty_content.slice(0..0),
+            empty_content.select([]).unwrap(),
+            buffer.slice(0..0),
+            buffer.slice(2..2),
+            buffer.select([]).unwrap(),
+            buffer.slice(0..1).select([]).unwrap(),
+        ] {
+            assert!(empty.is_empty());
+            assert_eq!(empty.len(), 0);
+            assert_eq!(empty.as_str(), "");
+            assert_eq!(empty.join("\n"), "");
+            assert_eq!(empty.to_string(), "");
+            assert!(empty.get(0).is_none());
+            assert_eq!(empty.iter().count(), 0);
+        }
+    }
+
+    #[test]
+    fn line_count_is_newline_count_plus_one() {
+        for content in ["", "\n", "a", "a\n", "\n\n", "a\nb", "a\nb\n"] {
+            let buffer = Buffer::new(content.to_string());
+            let index = CutIndex::build(content, |c| c == &b'\n');
+            assert_eq!(buffer.len(), content.matches('\n').count() + 1);
+            assert_eq!(buffer.len(), index.len());
+            assert_eq!(buffer.is_empty(), index.is_empty());
+        }
+    }
+
+    #[test]
+    fn sequential_and_parallel_construction_match() {
+        let content = (0..5000).map(|i| format!("Line {i}")).join("\n") + "\n";
+        let sequential = Buffer::new_sequential(content.clone());
+        let parallel = Buffer::builder()
+            .parallel_threshold(0)
+            .build(content.clone());
+        let default = Buffer::new(content);
+
+        assert_eq!(sequential.len(), parallel.len());
+        assert_eq!(sequential.len(), default.len());
+        assert!(sequential.byte_ranges().eq(parallel.byte_ranges()));
+        assert!(sequential.byte_ranges().eq(default.byte_ranges()));
+    }
+
+    #[test]
+    fn test_as_str_of_empty_slice() {
+        let buffer = Buffer::new("line 1\nline 2\nline 3".to_string());
+        assert_eq!(buffer.slice(1..1).as_str(), "");
+        assert_eq!(buffer.slice(5..9).as_str(), "");
+    }
+
+    #[test]
+    fn test_as_str_of_empty_selection() {
+        let buffer = Buffer::new("line 1\nline 2\nline 3".to_string());
+        assert_eq!(buffer.select([]).unwrap().as_str(), "");
+        assert_eq!(buffer.slice(1..3).select([]).unwrap().as_str(), "");
+    }
+
+    #[test]
+    fn test_as_str_of_selection_spans_selected_lines() {
+        let buffer = Buffer::new("line 1\nline 2\nline 3\nline 4".to_string());
+        assert_eq!(buffer.select([1]).unwrap().as_str(), "line 2");
+        assert_eq!(buffer.select([2, 1]).unwrap().as_str(), "line 2\nline 3");
+    }
+
     #[test]
     fn test_slice_then_select() {
         let content = "line 1\nline 2\nline 3\nline 4\nline 5".to_string();
@@ -0,0 +1,141 @@
+use itertools::Itertools;
+
+use super::{ArcSlice, Buffer};
+
+/// A column of optional values, one per line of a `Buffer`.
+///
+/// This is the shape of what extraction produces, e.g. `buffer.par_map(...)`
+/// with a closure returning `Option<T>`: `None` marks lines where the value
+/// was not found.
+///
+/// ```
+/// use analogz::containers::{Buffer, Column};
+///
+/// let logs = Buffer::new("took 12ms\nno timing\ntook 7ms".to_string());
+/// let took = Column::from(logs.par_map(|line| {
+///     let ms = line.as_str().strip_prefix("took ")?.strip_suffix("ms")?;
+///     ms.parse::<u32>().ok()
+/// }));
+/// assert_eq!(took.count_present(), 2);
+/// assert_eq!(took.to_buffer_selection(&logs).to_string(), "took 12ms\ntook 7ms");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Column<T> {
+    values: ArcSlice<Option<T>>,
+}
+
+impl<T> Column<T> {
+    pub fn new(values: ArcSlice<Option<T>>) -> Self {
+        Self { values }
+    }
+
+    pub fn into_inner(self) -> ArcSlice<Option<T>> {
+        self.values
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        self.values.get(idx)?.as_ref()
+    }
+
+    /// Returns the number of rows holding a value.
+    pub fn count_present(&self) -> usize {
+        self.values.iter().filter(|v| v.is_some()).count()
+    }
+
+    /// Returns the indices of the rows holding a value.
+    pub fn present_indices(&self) -> ArcSlice<usize> {
+        self.values
+            .iter()
+            .positions(Option::is_some)
+            .collect_vec()
+            .into()
+    }
+
+    /// Returns an iterator over the values that are present.
+    pub fn present(&self) -> impl Iterator<Item = &T> {
+        self.values.iter().flatten()
+    }
+
+    /// Applies `f` to every present value, keeping missing rows missing.
+    pub fn map<U, F>(&self, f: F) -> Column<U>
+    where
+        F: Fn(&T) -> U,
+    {
+        Column::new(
+            self.values
+                .iter()
+                .map(|v| v.as_ref().map(&f))
+                .collect_vec()
+                .into(),
+        )
+    }
+
+    /// Selects the lines of `buffer` for which this column holds a value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the column is longer than `buffer`, i.e. it was not computed
+    /// from it.
+    pub fn to_buffer_selection(&self, buffer: &Buffer) -> Buffer {
+        buffer
+            .select(self.present_indices().iter_cloned())
+            .expect("column is computed from the buffer")
+    }
+}
+
+impl<T> From<ArcSlice<Option<T>>> for Column<T> {
+    fn from(values: ArcSlice<Option<T>>) -> Self {
+        Self::new(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_codes(buffer: &Buffer) -> Column<u16> {
+        buffer
+            .par_map(|line| {
+                line.as_str()
+                    .split_once("status=")
+                    .and_then(|(_, rest)| rest.get(..3))
+                    .and_then(|code| code.parse().ok())
+            })
+            .into()
+    }
+
+    #[test]
+    fn selects_rows_where_extraction_succeeded() {
+        let buffer = Buffer::new(
+            "GET / status=200\nworker started\nGET /x status=404\nstatus=oops".to_string(),
+        );
+        let column = status_codes(&buffer);
+
+        assert_eq!(column.len(), 4);
+        assert_eq!(column.count_present(), 2);
+        assert_eq!(column.present_indices().as_slice(), &[0, 2]);
+        assert_eq!(column.present().copied().collect_vec(), [200, 404]);
+        assert_eq!(column.get(1), None);
+
+        let selected = column.to_buffer_selection(&buffer);
+        assert_eq!(selected.to_string(), "GET / status=200\nGET /x status=404");
+    }
+
+    #[test]
+    fn map_keeps_missing_rows() {
+        let column = Column::from(ArcSlice::new(vec![Some(200u16), None, Some(503)]));
+        let is_error = column.map(|code| *code >= 500);
+        assert_eq!(
+            is_error.into_inner().as_slice(),
+            &[Some(false), None, Some(true)]
+        );
+    }
+}
@@ -0,0 +1,27 @@
+use super::ArcStr;
+
+/// Types that can be parsed from a single log line.
+///
+/// Implement it to turn a [`Buffer`](super::Buffer) into typed records with
+/// [`Buffer::parse_records`](super::Buffer::parse_records). Returning `None`
+/// marks a line that does not hold a record.
+///
+/// ```
+/// use analogz::containers::{ArcStr, Buffer, FromLine};
+///
+/// struct Level(String);
+///
+/// impl FromLine for Level {
+///     fn from_line(line: &ArcStr) -> Option<Self> {
+///         let (level, _) = line.as_str().split_once(' ')?;
+///         Some(Level(level.to_string()))
+///     }
+/// }
+///
+/// let logs = Buffer::new("INFO started\nERROR failed".to_string());
+/// let levels = logs.parse_records::<Level>();
+/// assert_eq!(levels[1].as_ref().unwrap().0, "ERROR");
+/// ```
+pub trait FromLine: Sized {
+    fn from_line(line: &ArcStr) -> Option<Self>;
+}
@@ -1,9 +1,19 @@
 mod arc_slice;
 mod arc_str;
+mod buffer;
+mod column;
+mod from_line;
 mod pattern;
 
 pub use arc_slice::ArcSlice;
-pub use arc_str::ArcStr;
+pub use arc_slice::InvalidIndexError;
+pub use arc_str::{ArcStr, LocatedParseError};
+pub use buffer::{Buffer, BufferBuilder, BufferSummary, Line, LineTermination, Lines};
+pub use column::Column;
+pub use from_line::FromLine;
+pub use pattern::CaseInsensitive;
 pub use pattern::Pattern;
+pub use pattern::ReverseSearcher;
 pub use pattern::Searcher;
+pub use pattern::compile_regex;
 pub use regex::Regex;
@@ -1,6 +1,19 @@
 use regex::Regex;
 
-use crate::containers::ArcStr;
+use crate::{Error, containers::ArcStr};
+
+/// Compiles `pattern` into a [`Regex`], reporting why it is invalid through
+/// [`Error::InvalidPattern`].
+///
+/// ```
+/// use analogz::containers::compile_regex;
+///
+/// let err = compile_regex("(unclosed").unwrap_err();
+/// assert!(err.to_string().contains("unclosed group"));
+/// ```
+pub fn compile_regex(pattern: &str) -> Result<Regex, Error> {
+    Ok(Regex::new(pattern)?)
+}
 
 pub trait Pattern {
     type Searcher: Searcher;
@@ -12,16 +25,23 @@ pub trait Searcher {
     fn next_match(&mut self) -> Option<(usize, usize)>;
 }
 
+/// A [`Searcher`] that can also report matches from the end of the string,
+/// as used by [`ArcStr::rsplit`]. Forward and backward matches never overlap.
+pub trait ReverseSearcher: Searcher {
+    fn next_match_back(&mut self) -> Option<(usize, usize)>;
+}
+
 pub struct StrSearcher<'a> {
     astr: ArcStr,
     pat: &'a str,
     offset: usize,
+    end: usize,
 }
 
 impl<'a> Searcher for StrSearcher<'a> {
     fn next_match(&mut self) -> Option<(usize, usize)> {
         if !self.pat.is_empty()
-            && let Some(start) = self.astr.as_str()[self.offset..].find(self.pat)
+            && let Some(start) = self.astr.as_str()[self.offset..self.end].find(self.pat)
         {
             let start = self.offset + start;
             let end = start + self.pat.len();
@@ -33,11 +53,63 @@ impl<'a> Searcher for StrSearcher<'a> {
     }
 }
 
+impl<'a> ReverseSearcher for StrSearcher<'a> {
+    fn next_match_back(&mut self) -> Option<(usize, usize)> {
+        if !self.pat.is_empty()
+            && let Some(start) = self.astr.as_str()[self.offset..self.end].rfind(self.pat)
+        {
+            let start = self.offset + start;
+            self.end = start;
+            Some((start, start + self.pat.len()))
+        } else {
+            None
+        }
+    }
+}
+
 impl<'a> Pattern for &'a str {
     type Searcher = StrSearcher<'a>;
 
     fn into_searcher(self, astr: ArcStr) -> Self::Searcher {
         Self::Searcher {
+            end: astr.len(),
+            astr,
+            pat: self,
+            offset: 0,
+        }
+    }
+}
+
+pub struct CharSearcher {
+    astr: ArcStr,
+    pat: char,
+    offset: usize,
+    end: usize,
+}
+
+impl Searcher for CharSearcher {
+    fn next_match(&mut self) -> Option<(usize, usize)> {
+        let start = self.offset + self.astr.as_str()[self.offset..self.end].find(self.pat)?;
+        let end = start + self.pat.len_utf8();
+        self.offset = end;
+        Some((start, end))
+    }
+}
+
+impl ReverseSearcher for CharSearcher {
+    fn next_match_back(&mut self) -> Option<(usize, usize)> {
+        let start = self.offset + self.astr.as_str()[self.offset..self.end].rfind(self.pat)?;
+        self.end = start;
+        Some((start, start + self.pat.len_utf8()))
+    }
+}
+
+impl Pattern for char {
+    type Searcher = CharSearcher;
+
+    fn into_searcher(self, astr: ArcStr) -> Self::Searcher {
+        Self::Searcher {
+            end: astr.len(),
             astr,
             pat: self,
             offset: 0,
@@ -45,6 +117,88 @@ impl<'a> Pattern for &'a str {
     }
 }
 
+/// Searcher matching any single char of a set.
+pub struct CharSetSearcher<'a> {
+    astr: ArcStr,
+    pat: &'a [char],
+    offset: usize,
+    end: usize,
+}
+
+impl<'a> Searcher for CharSetSearcher<'a> {
+    fn next_match(&mut self) -> Option<(usize, usize)> {
+        let rest = &self.astr.as_str()[self.offset..self.end];
+        let (idx, c) = rest.char_indices().find(|(_, c)| self.pat.contains(c))?;
+        let start = self.offset + idx;
+        let end = start + c.len_utf8();
+        self.offset = end;
+        Some((start, end))
+    }
+}
+
+impl<'a> ReverseSearcher for CharSetSearcher<'a> {
+    fn next_match_back(&mut self) -> Option<(usize, usize)> {
+        let rest = &self.astr.as_str()[self.offset..self.end];
+        let (idx, c) = rest.char_indices().rfind(|(_, c)| self.pat.contains(c))?;
+        let start = self.offset + idx;
+        self.end = start;
+        Some((start, start + c.len_utf8()))
+    }
+}
+
+impl<'a> Pattern for &'a [char] {
+    type Searcher = CharSetSearcher<'a>;
+
+    fn into_searcher(self, astr: ArcStr) -> Self::Searcher {
+        Self::Searcher {
+            end: astr.len(),
+            astr,
+            pat: self,
+            offset: 0,
+        }
+    }
+}
+
+/// Pattern matching a string while ignoring ASCII case, so `"error"` matches
+/// `"ERROR"` and `"Error"`. Non-ASCII chars must match exactly, and, like a
+/// `&str` pattern, an empty string never matches.
+#[derive(Debug, Clone, Copy)]
+pub struct CaseInsensitive<'a>(pub &'a str);
+
+pub struct CaseInsensitiveSearcher<'a> {
+    astr: ArcStr,
+    pat: &'a str,
+    offset: usize,
+}
+
+impl<'a> Searcher for CaseInsensitiveSearcher<'a> {
+    fn next_match(&mut self) -> Option<(usize, usize)> {
+        let pat = self.pat.as_bytes();
+        if pat.is_empty() {
+            return None;
+        }
+        let start = self.offset
+            + self.astr.as_str().as_bytes()[self.offset..]
+                .windows(pat.len())
+                .position(|w| w.eq_ignore_ascii_case(pat))?;
+        let end = start + pat.len();
+        self.offset = end;
+        Some((start, end))
+    }
+}
+
+impl<'a> Pattern for CaseInsensitive<'a> {
+    type Searcher = CaseInsensitiveSearcher<'a>;
+
+    fn into_searcher(self, astr: ArcStr) -> Self::Searcher {
+        Self::Searcher {
+            astr,
+            pat: self.0,
+            offset: 0,
+        }
+    }
+}
+
 pub struct RegexSearcher {
     astr: ArcStr,
     pat: Regex,
@@ -79,3 +233,91 @@ impl Pattern for Regex {
         Self::Searcher { astr, pat: self }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts<P: Pattern>(input: &str, pat: P) -> Vec<String> {
+        ArcStr::from(input)
+            .split(pat)
+            .map(|s| s.as_str().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn compile_regex_reports_why_a_pattern_is_invalid() {
+        assert!(compile_regex(r"\d+").unwrap().is_match("id 42"));
+
+        let err = compile_regex("[a-").unwrap_err();
+        assert!(matches!(err, Error::InvalidPattern(_)));
+        let message = err.to_string();
+        assert!(message.starts_with("invalid pattern:"));
+        assert!(message.contains("unclosed character class"), "{message}");
+    }
+
+    #[test]
+    fn char_split_matches_str_split() {
+        for input in ["a,b,c", "abc", ",a,,b", "héllo,wörld", "日本,語"] {
+            assert_eq!(parts(input, ','), parts(input, ","));
+        }
+        assert_eq!(parts("a→b→c", '→'), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn char_set_split_matches_str_split_on_one_delimiter() {
+        for input in ["a,b;c", "k=v; x=y,z", ";;a", "é;ü,ö"] {
+            let normalized = input.replace(';', ",");
+            assert_eq!(parts(input, &[',', ';'][..]), parts(&normalized, ","));
+        }
+    }
+
+    #[test]
+    fn char_searchers_report_byte_offsets() {
+        let astr = ArcStr::from("héllo wörld");
+        assert_eq!(astr.find('w'), Some((7, 8)));
+        assert_eq!(astr.find(&['ö', 'l'][..]), Some((3, 4)));
+        assert_eq!(astr.slice(7..).find(&['ö', 'x'][..]), Some((1, 3)));
+        assert_eq!(astr.find('z'), None);
+        assert_eq!(astr.find(&[][..]), None);
+    }
+
+    #[test]
+    fn case_insensitive_matches_ascii_case_variants() {
+        let astr = ArcStr::from("Error: disk ERROR, café error");
+        let matches = std::iter::from_fn({
+            let mut searcher = CaseInsensitive("error").into_searcher(astr.clone());
+            move || searcher.next_match()
+        })
+        .collect::<Vec<_>>();
+        assert_eq!(matches, [(0, 5), (12, 17), (25, 30)]);
+        assert_eq!(ArcStr::from("CAFÉ").find(CaseInsensitive("café")), None);
+        assert_eq!(
+            ArcStr::from("Café").find(CaseInsensitive("cAFé")),
+            Some((0, 5))
+        );
+        assert_eq!(astr.find(CaseInsensitive("")), None);
+    }
+
+    #[test]
+    fn reverse_searchers_meet_forward_matches_without_overlap() {
+        fn both_ends<S: ReverseSearcher>(mut searcher: S) -> Vec<(usize, usize)> {
+            let mut matches = vec![searcher.next_match_back().unwrap()];
+            matches.extend(searcher.next_match());
+            matches.extend(searcher.next_match_back());
+            matches.extend(searcher.next_match());
+            matches
+        }
+        let astr = ArcStr::from("a,b,c");
+        assert_eq!(both_ends(",".into_searcher(astr.clone())), [(3, 4), (1, 2)]);
+        assert_eq!(both_ends(','.into_searcher(astr.clone())), [(3, 4), (1, 2)]);
+        let set = &[',', 'é'][..];
+        assert_eq!(
+            both_ends(set.into_searcher(ArcStr::from("aébé"))),
+            [(4, 6), (1, 3)]
+        );
+        let mut overlapping = "aa".into_searcher(ArcStr::from("aaa"));
+        assert_eq!(overlapping.next_match_back(), Some((1, 3)));
+        assert_eq!(overlapping.next_match(), None);
+    }
+}
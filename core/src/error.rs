@@ -0,0 +1,8 @@
+/// Errors reported by analogz.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// A regex pattern failed to compile. The message tells why, e.g. an
+    /// unclosed group or an unknown escape.
+    #[error("invalid pattern: {0}")]
+    InvalidPattern(#[from] regex::Error),
+}
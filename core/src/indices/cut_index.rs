@@ -3,7 +3,13 @@ use itertools::Itertools;
 use std::ops::Range;
 
 use crate::containers::ArcSlice;
+use crate::misc::parallelism::parallelism;
 
+/// Positions that cut a sequence into pieces, e.g. the newlines of a log.
+///
+/// A sequence with `n` cuts has `n + 1` pieces, so an empty input is one empty
+/// piece: `len()` is `1` and `is_empty()` is `false`. Only slicing can produce
+/// an index with no pieces. `Buffer` relies on this rule for its lines.
 #[derive(Debug, Clone)]
 pub struct CutIndex {
     indices: ArcSlice<usize>,
@@ -34,29 +40,62 @@ impl CutIndex {
         T: Sync,
         F: Fn(&T) -> bool + Send + Clone + 'static,
     {
-        let arr = arr.as_ref();
-        let indices = std::thread::scope(|scope| {
-            let chunk_size = num_cpus::get();
-            std::iter::once(0)
-                .chain(
-                    arr.chunks(chunk_size)
-                        .enumerate()
-                        .map(move |(idx, slice)| (idx * chunk_size, slice))
-                        .map(|(offset, slice)| {
-                            let f = f.clone();
-                            scope.spawn(move || {
-                                slice
-                                    .iter()
-                                    .enumerate()
-                                    .filter_map(move |(i, c)| (f(c)).then_some(offset + i))
+        Self::build_par_with_threads(arr, f, parallelism())
+    }
+
+    /// Same as [`CutIndex::build_par`], splitting the work over `threads`
+    /// threads instead of the global [`parallelism`].
+    #[cfg_attr(feature = "no-threads", allow(unused_variables))]
+    pub(crate) fn build_par_with_threads<T, F>(arr: impl AsRef<[T]>, f: F, threads: usize) -> Self
+    where
+        T: Sync,
+        F: Fn(&T) -> bool + Send + Clone + 'static,
+    {
+        #[cfg(feature = "no-threads")]
+        {
+            Self::build(arr, f)
+        }
+        #[cfg(not(feature = "no-threads"))]
+        {
+            let arr = arr.as_ref();
+            let indices = std::thread::scope(|scope| {
+                let chunk_size = arr.len().div_ceil(threads.max(1)).max(1);
+                std::iter::once(0)
+                    .chain(
+                        arr.chunks(chunk_size)
+                            .enumerate()
+                            .map(move |(idx, slice)| (idx * chunk_size, slice))
+                            .map(|(offset, slice)| {
+                                let f = f.clone();
+                                scope.spawn(move || {
+                                    slice
+                                        .iter()
+                                        .enumerate()
+                                        .filter_map(move |(i, c)| (f(c)).then_some(offset + i))
+                                        .collect_vec()
+                                })
                             })
-                        })
-                        .flat_map(|hndl| hndl.join().unwrap()),
-                )
-                .chain([arr.len()])
-                .collect_vec()
-        });
+                            .collect_vec()
+                            .into_iter()
+                            .flat_map(|hndl| hndl.join().unwrap()),
+                    )
+                    .chain([arr.len()])
+                    .collect_vec()
+            });
+
+            CutIndex {
+                indices: indices.into(),
+            }
+        }
+    }
 
+    /// Creates an index from already known cut positions of a sequence of
+    /// `len` items. `cuts` must be sorted and below `len`.
+    pub fn from_cuts(cuts: Vec<usize>, len: usize) -> Self {
+        debug_assert!(cuts.is_sorted() && cuts.last().is_none_or(|c| *c < len));
+        let mut indices = cuts;
+        indices.insert(0, 0);
+        indices.push(len);
         CutIndex {
             indices: indices.into(),
         }
@@ -64,10 +103,16 @@ impl CutIndex {
 
     pub fn slice(&self, rng: Range<usize>) -> Self {
         CutIndex {
-            indices: self.indices.slice(rng.start..rng.end + 1),
+            indices: self.indices.slice(rng.start..rng.end.saturating_add(1)),
         }
     }
 
+    /// Returns the position of the first piece in the index this one was
+    /// sliced from, or `0` if it was not sliced.
+    pub fn offset(&self) -> usize {
+        self.indices.start()
+    }
+
     pub fn start(&self, idx: usize) -> Option<usize> {
         if self.indices.start() + idx == 0 {
             self.indices.get(idx).copied()
@@ -84,10 +129,30 @@ impl CutIndex {
         Some(self.start(idx)?..self.end(idx)?)
     }
 
+    /// Returns the pieces overlapping the positions in `rng`, each piece
+    /// owning the cut that ends it. An empty `rng` overlaps no piece.
+    pub fn overlapping(&self, rng: Range<usize>) -> Range<usize> {
+        if rng.is_empty() {
+            return 0..0;
+        }
+        let cuts = self.indices.as_slice();
+        let first = cuts
+            .get(1..)
+            .unwrap_or_default()
+            .partition_point(|end| *end < rng.start);
+        let mut last = cuts[..self.len()].partition_point(|cut| cut + 1 < rng.end);
+        if self.offset() == 0 && !self.is_empty() {
+            last = last.max(1);
+        }
+        first..last.max(first)
+    }
+
+    /// Returns the number of pieces.
     pub fn len(&self) -> usize {
         self.indices.len().saturating_sub(1)
     }
 
+    /// Returns `true` if there are no pieces, which only happens after `slice`.
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
@@ -118,6 +183,23 @@ mod tests {
         false
     }
 
+    #[test]
+    fn test_overlapping_pieces() {
+        // pieces: "a" 0..1, "bc" 2..4, "d" 5..6, "" 7..7
+        let cut_indices = CutIndex::build(test_chars(), is_newline);
+        assert_eq!(cut_indices.overlapping(0..1), 0..1);
+        assert_eq!(cut_indices.overlapping(1..2), 0..1);
+        assert_eq!(cut_indices.overlapping(3..6), 1..3);
+        assert_eq!(cut_indices.overlapping(0..100), 0..4);
+        assert_eq!(cut_indices.overlapping(7..8), 3..4);
+        assert_eq!(cut_indices.overlapping(50..60), 4..4);
+        assert_eq!(cut_indices.overlapping(3..3), 0..0);
+
+        let sliced = cut_indices.slice(1..3);
+        assert_eq!(sliced.overlapping(0..3), 0..1);
+        assert_eq!(sliced.overlapping(0..100), 0..2);
+    }
+
     // Tests for build()
     #[test]
     fn test_build_empty_array() {
@@ -272,6 +354,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_build_par_single_thread_matches_multi_thread() {
+        let data: Vec<char> = (0..997)
+            .map(|i| if i % 7 == 0 { '\n' } else { 'x' })
+            .collect();
+        let single = CutIndex::build_par_with_threads(&data, is_newline, 1);
+        for threads in [2, 3, 8, 64] {
+            let multi = CutIndex::build_par_with_threads(&data, is_newline, threads);
+            assert_eq!(multi.len(), single.len());
+            for i in 0..single.len() {
+                assert_eq!(multi.range(i), single.range(i), "threads {threads}");
+            }
+        }
+    }
+
     // Tests for slice()
     #[test]
     fn test_slice_full_range() {
@@ -1,2 +1,4 @@
 mod cut_index;
 mod range_index;
+
+pub use cut_index::CutIndex;
@@ -1,3 +1,22 @@
+//! Fast, cheap-to-clone containers for analyzing log files.
+//!
+//! This is the Rust API of analogz: it has no dependency on Python. The
+//! bindings live in the separate `py-bind` crate, and the extractors (date
+//! times, matches, ...) in `analogz-extractor`.
+//!
+//! ```
+//! use analogz::containers::Buffer;
+//!
+//! let logs = Buffer::new("INFO start\nERROR boom".to_string());
+//! let errors = logs.map(|line| line.as_str().starts_with("ERROR"));
+//! assert_eq!(errors.as_slice(), &[false, true]);
+//! ```
 pub mod containers;
+mod error;
 // pub mod extractors;
+mod indices;
 pub mod misc;
+pub mod prelude;
+
+pub use error::Error;
+pub use misc::{grep::grep_reader, parallelism::set_parallelism};
@@ -13,6 +13,12 @@ impl Iterator for Chars {
     }
 }
 
+impl DoubleEndedIterator for Chars {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.char_indices.next_back().map(|(_, c)| c)
+    }
+}
+
 impl From<ArcStr> for Chars {
     fn from(value: ArcStr) -> Self {
         Self {
@@ -25,26 +31,66 @@ impl From<ArcStr> for Chars {
 pub struct CharIndices {
     astr: ArcStr,
     offset: usize,
+    back: usize,
 }
 
 impl Iterator for CharIndices {
     type Item = (usize, char);
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.astr.as_str()[self.offset..].chars().next().map(|c| {
-            let o = self.offset;
-            let l = c.len_utf8();
-            self.offset += l;
-            (o, c)
-        })
+        self.astr.as_str()[self.offset..self.back]
+            .chars()
+            .next()
+            .map(|c| {
+                let o = self.offset;
+                let l = c.len_utf8();
+                self.offset += l;
+                (o, c)
+            })
+    }
+}
+
+impl DoubleEndedIterator for CharIndices {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.astr.as_str()[self.offset..self.back]
+            .chars()
+            .next_back()
+            .map(|c| {
+                self.back -= c.len_utf8();
+                (self.back, c)
+            })
     }
 }
 
 impl From<ArcStr> for CharIndices {
     fn from(value: ArcStr) -> Self {
         CharIndices {
+            back: value.len(),
             astr: value,
             offset: 0,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_char_indices_rev_multibyte() {
+        let astr = ArcStr::from("-aé日😀").slice(1..);
+        let rev = CharIndices::from(astr.clone()).rev().collect::<Vec<_>>();
+        assert_eq!(rev, [(6, '😀'), (3, '日'), (1, 'é'), (0, 'a')]);
+        assert_eq!(Chars::from(astr).rev().collect::<String>(), "😀日éa");
+    }
+
+    #[test]
+    fn test_char_indices_both_ends_meet() {
+        let mut iter = CharIndices::from(ArcStr::from("aéb"));
+        assert_eq!(iter.next_back(), Some((3, 'b')));
+        assert_eq!(iter.next(), Some((0, 'a')));
+        assert_eq!(iter.next_back(), Some((1, 'é')));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+}
@@ -0,0 +1,250 @@
+use std::ops::Range;
+
+/// An edit turning one sequence into another, as produced by [`myers`].
+///
+/// `Equal` and `Delete` hold an index into the old sequence, `Insert` an index
+/// into the new one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffOp {
+    Equal(usize),
+    Insert(usize),
+    Delete(usize),
+}
+
+/// Computes a shortest edit script between an old sequence of length `n` and a
+/// new one of length `m` with the Myers diff algorithm.
+///
+/// `eq(i, j)` tells whether item `i` of the old sequence equals item `j` of
+/// the new one. Uses the linear-space variant, which splits the sequences at
+/// the middle of an optimal path, so it runs in O((n + m) * d) time, where `d`
+/// is the number of edits, and O(n + m) memory. Within a run of edits,
+/// deletions come before insertions.
+///
+/// ```
+/// use analogz::misc::diff::{DiffOp, myers};
+///
+/// let (old, new) = (["a", "b", "c"], ["a", "c", "d"]);
+/// let ops = myers(old.len(), new.len(), |i, j| old[i] == new[j]);
+/// assert_eq!(
+///     ops,
+///     [DiffOp::Equal(0), DiffOp::Delete(1), DiffOp::Equal(2), DiffOp::Insert(2)]
+/// );
+/// ```
+pub fn myers(n: usize, m: usize, eq: impl Fn(usize, usize) -> bool) -> Vec<DiffOp> {
+    let len = 2 * (n + m).div_ceil(2) + 3;
+    let (mut vf, mut vb) = (vec![0; len], vec![0; len]);
+    let mut ops = Vec::with_capacity(n.max(m));
+    conquer(&eq, 0..n, 0..m, &mut vf, &mut vb, &mut ops);
+    for run in
+        ops.chunk_by_mut(|a, b| !matches!(a, DiffOp::Equal(_)) && !matches!(b, DiffOp::Equal(_)))
+    {
+        run.sort_by_key(|op| matches!(op, DiffOp::Insert(_)));
+    }
+    ops
+}
+
+/// Appends the edit script of `old` to `new` to `ops`, splitting both ranges
+/// at a middle snake until one of them is empty.
+fn conquer(
+    eq: &impl Fn(usize, usize) -> bool,
+    mut old: Range<usize>,
+    mut new: Range<usize>,
+    vf: &mut [usize],
+    vb: &mut [usize],
+    ops: &mut Vec<DiffOp>,
+) {
+    let prefix = old
+        .clone()
+        .zip(new.clone())
+        .take_while(|&(i, j)| eq(i, j))
+        .count();
+    ops.extend((old.start..old.start + prefix).map(DiffOp::Equal));
+    old.start += prefix;
+    new.start += prefix;
+    let suffix = old
+        .clone()
+        .rev()
+        .zip(new.clone().rev())
+        .take_while(|&(i, j)| eq(i, j))
+        .count();
+    old.end -= suffix;
+    new.end -= suffix;
+
+    if old.is_empty() {
+        ops.extend(new.map(DiffOp::Insert));
+    } else if new.is_empty() {
+        ops.extend(old.clone().map(DiffOp::Delete));
+    } else {
+        let (x, y) = middle_snake(eq, old.clone(), new.clone(), vf, vb);
+        conquer(eq, old.start..x, new.start..y, vf, vb, ops);
+        conquer(eq, x..old.end, y..new.end, vf, vb, ops);
+    }
+    ops.extend((old.end..old.end + suffix).map(DiffOp::Equal));
+}
+
+/// Returns the start of the middle snake of an optimal path from the start of
+/// `old` and `new` to their end, searching from both ends at once.
+///
+/// `vf` and `vb` hold the furthest reaching `x` of each diagonal for the
+/// forward and the backward search; their previous content is ignored.
+fn middle_snake(
+    eq: &impl Fn(usize, usize) -> bool,
+    old: Range<usize>,
+    new: Range<usize>,
+    vf: &mut [usize],
+    vb: &mut [usize],
+) -> (usize, usize) {
+    let (n, m) = (old.len(), new.len());
+    let delta = n as isize - m as isize;
+    let odd = delta % 2 != 0;
+    let offset = (vf.len() / 2) as isize;
+    let at = |k: isize| (k + offset) as usize;
+    vf[at(1)] = 0;
+    vb[at(1)] = 0;
+
+    for d in 0..=(n + m).div_ceil(2) as isize {
+        for k in (-d..=d).rev().step_by(2) {
+            let mut x = if k == -d || (k != d && vf[at(k - 1)] < vf[at(k + 1)]) {
+                vf[at(k + 1)]
+            } else {
+                vf[at(k - 1)] + 1
+            };
+            let y = (x as isize - k) as usize;
+            let (x0, y0) = (x, y);
+            x += (x..n)
+                .zip(y..m)
+                .take_while(|&(i, j)| eq(old.start + i, new.start + j))
+                .count();
+            vf[at(k)] = x;
+            if odd && (k - delta).abs() < d && x + vb[at(delta - k)] >= n {
+                return (old.start + x0, new.start + y0);
+            }
+        }
+        for k in (-d..=d).rev().step_by(2) {
+            let mut x = if k == -d || (k != d && vb[at(k - 1)] < vb[at(k + 1)]) {
+                vb[at(k + 1)]
+            } else {
+                vb[at(k - 1)] + 1
+            };
+            let y = (x as isize - k) as usize;
+            x += (x..n)
+                .zip(y..m)
+                .take_while(|&(i, j)| eq(old.end - 1 - i, new.end - 1 - j))
+                .count();
+            vb[at(k)] = x;
+            if !odd && (k - delta).abs() <= d && x + vf[at(delta - k)] >= n {
+                let y = (x as isize - k) as usize;
+                return (old.end - x, new.end - y);
+            }
+        }
+    }
+    unreachable!("the searches meet within (n + m) / 2 edits")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::misc::prng::SplitMix64;
+
+    fn diff(old: &str, new: &str) -> Vec<DiffOp> {
+        let (old, new) = (old.as_bytes(), new.as_bytes());
+        myers(old.len(), new.len(), |i, j| old[i] == new[j])
+    }
+
+    fn apply(old: &str, new: &str, ops: &[DiffOp]) -> String {
+        ops.iter()
+            .filter_map(|op| match op {
+                DiffOp::Equal(i) => Some(old.as_bytes()[*i] as char),
+                DiffOp::Insert(j) => Some(new.as_bytes()[*j] as char),
+                DiffOp::Delete(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn empty_sequences() {
+        assert!(diff("", "").is_empty());
+        assert_eq!(diff("ab", ""), [DiffOp::Delete(0), DiffOp::Delete(1)]);
+        assert_eq!(diff("", "ab"), [DiffOp::Insert(0), DiffOp::Insert(1)]);
+    }
+
+    #[test]
+    fn identical_sequences_are_all_equal() {
+        assert_eq!(
+            diff("abc", "abc"),
+            [DiffOp::Equal(0), DiffOp::Equal(1), DiffOp::Equal(2)]
+        );
+    }
+
+    #[test]
+    fn edit_scripts_are_minimal_and_rebuild_the_new_sequence() {
+        for (old, new, edits) in [
+            ("abcabba", "cbabac", 5),
+            ("kitten", "sitting", 5),
+            ("abc", "xyz", 6),
+            ("a", "ba", 1),
+        ] {
+            let ops = diff(old, new);
+            assert_eq!(apply(old, new, &ops), new);
+            let count = ops
+                .iter()
+                .filter(|op| !matches!(op, DiffOp::Equal(_)))
+                .count();
+            assert_eq!(count, edits, "{old} -> {new}");
+        }
+    }
+
+    #[test]
+    fn edit_scripts_match_the_lcs_length_on_random_input() {
+        fn lcs(old: &[u8], new: &[u8]) -> usize {
+            let mut row = vec![0; new.len() + 1];
+            for a in old {
+                let mut diag = 0;
+                for (j, b) in new.iter().enumerate() {
+                    let up = row[j + 1];
+                    row[j + 1] = if a == b { diag + 1 } else { up.max(row[j]) };
+                    diag = up;
+                }
+            }
+            row[new.len()]
+        }
+
+        let mut rng = SplitMix64::new(7);
+        for _ in 0..500 {
+            let mut text = || {
+                let len = rng.below(30);
+                (0..len)
+                    .map(|_| b"abc"[rng.below(3)] as char)
+                    .collect::<String>()
+            };
+            let (old, new) = (text(), text());
+            let ops = diff(&old, &new);
+            assert_eq!(apply(&old, &new, &ops), new, "{old} -> {new}");
+            let equal = ops
+                .iter()
+                .filter(|op| matches!(op, DiffOp::Equal(_)))
+                .count();
+            assert_eq!(equal, lcs(old.as_bytes(), new.as_bytes()), "{old} -> {new}");
+        }
+    }
+
+    #[test]
+    fn many_edits_on_long_sequences() {
+        let old = (0..4000).map(|i| i * 2).collect::<Vec<_>>();
+        let new = (0..4000)
+            .map(|i| i * 2 + (i / 1000) % 2)
+            .collect::<Vec<_>>();
+        let ops = myers(old.len(), new.len(), |i, j| old[i] == new[j]);
+        let count = |f: fn(&DiffOp) -> bool| ops.iter().filter(|op| f(op)).count();
+        assert_eq!(count(|op| matches!(op, DiffOp::Equal(_))), 2000);
+        assert_eq!(count(|op| matches!(op, DiffOp::Delete(_))), 2000);
+        assert_eq!(count(|op| matches!(op, DiffOp::Insert(_))), 2000);
+
+        let a = (0..2000).collect::<Vec<_>>();
+        let b = (2000..4000).collect::<Vec<_>>();
+        let ops = myers(a.len(), b.len(), |i, j| a[i] == b[j]);
+        assert!(ops[..2000].iter().all(|op| matches!(op, DiffOp::Delete(_))));
+        assert!(ops[2000..].iter().all(|op| matches!(op, DiffOp::Insert(_))));
+        assert_eq!(ops.len(), 4000);
+    }
+}
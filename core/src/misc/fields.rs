@@ -0,0 +1,116 @@
+use crate::containers::ArcStr;
+
+/// Iterator over the delimited fields of an `ArcStr`, yielding each field
+/// together with its 0-based index.
+///
+/// Created by [`ArcStr::fields`].
+#[derive(Debug)]
+pub struct Fields {
+    astr: ArcStr,
+    delim: char,
+    quote: Option<char>,
+    offset: usize,
+    index: usize,
+    done: bool,
+}
+
+impl Fields {
+    pub fn new(astr: ArcStr, delim: char, quote: Option<char>) -> Self {
+        Self {
+            astr,
+            delim,
+            quote,
+            offset: 0,
+            index: 0,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for Fields {
+    type Item = (usize, ArcStr);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let start = self.offset;
+        let mut quoted = false;
+        let end = self.astr.as_str()[start..]
+            .char_indices()
+            .find(|(_, c)| {
+                if Some(*c) == self.quote {
+                    quoted = !quoted;
+                }
+                !quoted && *c == self.delim
+            })
+            .map(|(i, _)| start + i);
+        let field = match end {
+            Some(end) => {
+                self.offset = end + self.delim.len_utf8();
+                self.astr.slice(start..end)
+            }
+            None => {
+                self.done = true;
+                self.astr.slice(start..)
+            }
+        };
+        let index = self.index;
+        self.index += 1;
+        Some((index, field))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(input: &str, delim: char, quote: Option<char>) -> Vec<(usize, String)> {
+        ArcStr::from(input)
+            .fields(delim, quote)
+            .map(|(i, f)| (i, f.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn simple_comma_row() {
+        assert_eq!(
+            fields("a,b,c", ',', None),
+            vec![(0, "a".into()), (1, "b".into()), (2, "c".into())]
+        );
+    }
+
+    #[test]
+    fn empty_fields_are_kept() {
+        assert_eq!(
+            fields(",x,", ',', None),
+            vec![(0, "".into()), (1, "x".into()), (2, "".into())]
+        );
+    }
+
+    #[test]
+    fn quoted_field_keeps_delimiter() {
+        assert_eq!(
+            fields(r#"1,"x, y",3"#, ',', Some('"')),
+            vec![(0, "1".into()), (1, r#""x, y""#.into()), (2, "3".into())]
+        );
+    }
+
+    #[test]
+    fn quote_is_ignored_when_not_supplied() {
+        assert_eq!(
+            fields(r#""x, y""#, ',', None),
+            vec![(0, r#""x"#.into()), (1, r#" y""#.into())]
+        );
+    }
+
+    #[test]
+    fn offsets_are_preserved() {
+        let line = ArcStr::from("ts=1;level=info;msg=ok").slice(5..);
+        let got = line
+            .fields(';', None)
+            .map(|(i, f)| (i, f.start(), f.end()))
+            .collect::<Vec<_>>();
+        assert_eq!(got, vec![(0, 5, 15), (1, 16, 22)]);
+    }
+}
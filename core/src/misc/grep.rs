@@ -0,0 +1,104 @@
+use std::io::{self, BufRead};
+
+use regex::Regex;
+
+/// Streams the lines of `reader` and calls `sink(line_no, line)` for each line
+/// matching `re`, without loading the whole input in memory.
+///
+/// Line numbers start at `0`, like `Buffer` indices. Lines are split on `\n`;
+/// the `\n`, or the `\r\n` of CRLF input, is not part of the line passed to
+/// `sink`. Invalid UTF-8 is replaced with `U+FFFD`, as in
+/// `Buffer::from_bytes_lossy`, so a stray bad byte does not stop the scan.
+/// Only the current line is held in memory. This is the low-memory
+/// counterpart of filtering a `Buffer`.
+///
+/// # Errors
+///
+/// Fails with the first error of `reader`.
+///
+/// ```
+/// use analogz::{containers::Regex, grep_reader};
+///
+/// let logs = "INFO start\nERROR boom\nINFO done\n";
+/// let mut errors = Vec::new();
+/// grep_reader(logs.as_bytes(), &Regex::new("ERROR").unwrap(), |no, line| {
+///     errors.push((no, line.to_string()))
+/// })
+/// .unwrap();
+/// assert_eq!(errors, [(1, "ERROR boom".to_string())]);
+/// ```
+pub fn grep_reader(
+    mut reader: impl BufRead,
+    re: &Regex,
+    mut sink: impl FnMut(usize, &str),
+) -> io::Result<()> {
+    let mut line = Vec::new();
+    let mut line_no = 0;
+    while reader.read_until(b'\n', &mut line)? > 0 {
+        let content = match line.strip_suffix(b"\n") {
+            Some(content) => content.strip_suffix(b"\r").unwrap_or(content),
+            None => &line,
+        };
+        let content = String::from_utf8_lossy(content);
+        if re.is_match(&content) {
+            sink(line_no, &content);
+        }
+        line.clear();
+        line_no += 1;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_grep_reader_line_numbers() {
+        let logs = "ERROR a\nINFO b\n\nWARN c\nERROR d\nERROR e";
+        let re = Regex::new("^ERROR").unwrap();
+        let mut matched = Vec::new();
+        grep_reader(Cursor::new(logs), &re, |no, line| {
+            matched.push((no, line.to_string()))
+        })
+        .unwrap();
+        assert_eq!(
+            matched,
+            [
+                (0, "ERROR a".to_string()),
+                (4, "ERROR d".to_string()),
+                (5, "ERROR e".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_grep_reader_empty_lines() {
+        let re = Regex::new("^$").unwrap();
+        let mut matched = Vec::new();
+        grep_reader(Cursor::new("a\n\nb\n"), &re, |no, _| matched.push(no)).unwrap();
+        assert_eq!(matched, [1]);
+    }
+
+    #[test]
+    fn test_grep_reader_invalid_utf8_and_crlf() {
+        let logs = b"ERROR a\r\nbad \xF0\x90 byte\r\nERROR \xFF b\nERROR c\r";
+        let re = Regex::new("ERROR|bad").unwrap();
+        let mut matched = Vec::new();
+        grep_reader(Cursor::new(logs), &re, |no, line| {
+            matched.push((no, line.to_string()))
+        })
+        .unwrap();
+        assert_eq!(
+            matched,
+            [
+                (0, "ERROR a".to_string()),
+                (1, "bad \u{FFFD} byte".to_string()),
+                (2, "ERROR \u{FFFD} b".to_string()),
+                (3, "ERROR c\r".to_string())
+            ]
+        );
+    }
+}
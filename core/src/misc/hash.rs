@@ -0,0 +1,65 @@
+use std::hash::Hasher;
+
+const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The 64-bit FNV-1a hasher.
+///
+/// Unlike the std `DefaultHasher`, it is unseeded and its output is fixed, so
+/// hashes are reproducible across runs, processes and versions of Rust. It is
+/// fast on short inputs like log lines, but not resistant to collision attacks.
+///
+/// ```
+/// use analogz::misc::hash::Fnv1a;
+/// use std::hash::Hasher;
+///
+/// let mut hasher = Fnv1a::default();
+/// hasher.write(b"a");
+/// assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(OFFSET_BASIS)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fnv(bytes: &[u8]) -> u64 {
+        let mut hasher = Fnv1a::default();
+        hasher.write(bytes);
+        hasher.finish()
+    }
+
+    #[test]
+    fn matches_reference_values() {
+        assert_eq!(fnv(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn split_writes_hash_like_one_write() {
+        let mut hasher = Fnv1a::default();
+        hasher.write(b"foo");
+        hasher.write(b"bar");
+        assert_eq!(hasher.finish(), fnv(b"foobar"));
+    }
+}
@@ -1,8 +1,17 @@
 pub mod chars;
+pub mod diff;
+pub mod fields;
 // pub mod find_all;
+pub mod grep;
+pub mod hash;
 // pub mod ngrams;
+pub mod parallelism;
+pub mod prng;
 pub mod round_robin;
 pub mod split;
+pub mod stats;
+pub mod stepped_range;
+pub mod template;
+pub mod token_borders;
+pub mod tokens;
 pub mod window;
-// pub mod stepped_range;
-// pub mod token_borders;
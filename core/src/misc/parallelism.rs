@@ -0,0 +1,30 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static PARALLELISM: AtomicUsize = AtomicUsize::new(0);
+
+/// Sets the number of threads used by the parallel operations of the crate
+/// (`Buffer::par_map`, `CutIndex::build_par`, ...).
+///
+/// Passing `0` restores the default, which is the number of logical CPUs.
+/// With the `no-threads` feature the parallel operations always run on the
+/// calling thread and this setting has no effect.
+///
+/// ```
+/// use analogz::misc::parallelism::{parallelism, set_parallelism};
+///
+/// set_parallelism(2);
+/// assert_eq!(parallelism(), 2);
+/// set_parallelism(0);
+/// assert_eq!(parallelism(), num_cpus::get());
+/// ```
+pub fn set_parallelism(threads: usize) {
+    PARALLELISM.store(threads, Ordering::Relaxed);
+}
+
+/// Returns the number of threads the parallel operations will use.
+pub fn parallelism() -> usize {
+    match PARALLELISM.load(Ordering::Relaxed) {
+        0 => num_cpus::get(),
+        threads => threads,
+    }
+}
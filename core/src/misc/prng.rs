@@ -0,0 +1,45 @@
+/// A small, fast, seedable pseudo random number generator (SplitMix64).
+///
+/// It is not cryptographically secure; it exists so that sampling helpers can
+/// produce reproducible results without pulling in an external crate.
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `0..bound`. `bound` must be greater than zero.
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let a = (0..16).scan(SplitMix64::new(7), |r, _| Some(r.next_u64()));
+        let b = (0..16).scan(SplitMix64::new(7), |r, _| Some(r.next_u64()));
+        assert!(a.eq(b));
+    }
+
+    #[test]
+    fn below_stays_in_bound() {
+        let mut rng = SplitMix64::new(42);
+        assert!((0..1000).all(|_| rng.below(13) < 13));
+    }
+}
@@ -1,10 +1,12 @@
-use crate::containers::{ArcStr, Pattern, Searcher};
+use crate::containers::{ArcStr, Pattern, ReverseSearcher, Searcher};
 
 #[derive(Debug)]
 pub struct Split<S> {
     astr: ArcStr,
     ser: S,
     start: usize,
+    finished: bool,
+    trailing_empty: bool,
 }
 
 impl<S> Split<S> {
@@ -16,6 +18,20 @@ impl<S> Split<S> {
             astr: astr.clone(),
             ser: pat.into_searcher(astr),
             start: 0,
+            finished: false,
+            trailing_empty: true,
+        }
+    }
+
+    /// Like [`Split::new`], but without the empty field that follows a
+    /// trailing delimiter, as in `str::split_terminator`.
+    pub fn terminator<P>(astr: ArcStr, pat: P) -> Self
+    where
+        P: Pattern<Searcher = S>,
+    {
+        Self {
+            trailing_empty: false,
+            ..Self::new(astr, pat)
         }
     }
 }
@@ -27,23 +43,71 @@ where
     type Item = ArcStr;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
         if let Some((start, end)) = self.ser.next_match() {
             let next = self.astr.slice(self.start..start);
             self.start = end;
             Some(next)
         } else {
             let len = self.astr.len();
-            if self.start < len {
-                let next = self.astr.slice(self.start..len);
-                self.start = len;
-                Some(next)
-            } else {
+            self.finished = true;
+            if !self.trailing_empty && self.start == len {
                 None
+            } else {
+                Some(self.astr.slice(self.start..len))
             }
         }
     }
 }
 
+/// Iterator over the fields of a string from the last one to the first, as
+/// in `str::rsplit`. Matches are searched from the end, so taking the last
+/// field does not scan the rest of the string.
+#[derive(Debug)]
+pub struct RSplit<S> {
+    astr: ArcStr,
+    ser: S,
+    end: usize,
+    finished: bool,
+}
+
+impl<S> RSplit<S> {
+    pub fn new<P>(astr: ArcStr, pat: P) -> Self
+    where
+        P: Pattern<Searcher = S>,
+    {
+        Self {
+            end: astr.len(),
+            astr: astr.clone(),
+            ser: pat.into_searcher(astr),
+            finished: false,
+        }
+    }
+}
+
+impl<S> Iterator for RSplit<S>
+where
+    S: ReverseSearcher,
+{
+    type Item = ArcStr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        if let Some((start, end)) = self.ser.next_match_back() {
+            let next = self.astr.slice(end..self.end);
+            self.end = start;
+            Some(next)
+        } else {
+            self.finished = true;
+            Some(self.astr.slice(0..self.end))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -146,4 +210,20 @@ mod tests {
         let long = "x".repeat(10_000);
         assert_eq!(parts(&long, ","), vec![long]);
     }
+
+    fn terminated(input: &str, pat: &str) -> Vec<String> {
+        arc(input)
+            .split_terminator(pat)
+            .map(|s| s.as_ref().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn split_terminator_drops_only_trailing_empty_field() {
+        assert_eq!(terminated("a,b,", ","), vec!["a", "b"]);
+        assert_eq!(terminated("a,b", ","), vec!["a", "b"]);
+        assert_eq!(terminated(",a,,b,", ","), vec!["", "a", "", "b"]);
+        assert_eq!(terminated(",,", ","), vec!["", ""]);
+        assert!(terminated("", ",").is_empty());
+    }
 }
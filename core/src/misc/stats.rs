@@ -0,0 +1,108 @@
+use itertools::Itertools;
+
+use crate::containers::ArcSlice;
+
+/// Returns the quantile of the present values of `col` for each `q` in `qs`.
+///
+/// Quantiles are linearly interpolated between the two closest values, so the
+/// median of `[1, 2, 3, 4]` is `2.5`. A `q` outside `0.0..=1.0` yields `NaN`,
+/// as does every `q` of a column without present values.
+///
+/// ```
+/// use analogz::{containers::ArcSlice, misc::stats::quantiles};
+///
+/// let latency = ArcSlice::new(vec![Some(30.0), None, Some(10.0), Some(20.0)]);
+/// assert_eq!(quantiles(&latency, &[0.0, 0.5, 1.0]), [10.0, 20.0, 30.0]);
+/// ```
+pub fn quantiles(col: &ArcSlice<Option<f64>>, qs: &[f64]) -> Vec<f64> {
+    let sorted = col
+        .iter()
+        .flatten()
+        .copied()
+        .sorted_by(f64::total_cmp)
+        .collect_vec();
+    qs.iter()
+        .map(|q| {
+            if sorted.is_empty() || !(0.0..=1.0).contains(q) {
+                return f64::NAN;
+            }
+            let pos = q * (sorted.len() - 1) as f64;
+            let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+            sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+        })
+        .collect()
+}
+
+/// Returns the mean of the present values of `col`, or `NaN` if there are
+/// none.
+pub fn mean(col: &ArcSlice<Option<f64>>) -> f64 {
+    let (sum, count) = col
+        .iter()
+        .flatten()
+        .fold((0.0, 0usize), |(sum, count), v| (sum + v, count + 1));
+    if count == 0 {
+        f64::NAN
+    } else {
+        sum / count as f64
+    }
+}
+
+/// Returns the sample standard deviation (divided by `n - 1`) of the present
+/// values of `col`, or `NaN` with fewer than two present values.
+pub fn stddev(col: &ArcSlice<Option<f64>>) -> f64 {
+    let mean = mean(col);
+    let (sq_sum, count) = col
+        .iter()
+        .flatten()
+        .fold((0.0, 0usize), |(sq_sum, count), v| {
+            (sq_sum + (v - mean).powi(2), count + 1)
+        });
+    if count < 2 {
+        f64::NAN
+    } else {
+        (sq_sum / (count - 1) as f64).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn latencies() -> ArcSlice<Option<f64>> {
+        ArcSlice::new(vec![
+            Some(5.0),
+            None,
+            Some(1.0),
+            Some(4.0),
+            None,
+            Some(2.0),
+            Some(3.0),
+        ])
+    }
+
+    #[test]
+    fn test_quantiles() {
+        let col = latencies();
+        assert_eq!(quantiles(&col, &[0.5]), [3.0]);
+        assert_eq!(quantiles(&col, &[0.0, 0.25, 1.0]), [1.0, 2.0, 5.0]);
+        assert_eq!(quantiles(&col, &[0.9]), [4.6]);
+        assert!(quantiles(&col, &[1.5])[0].is_nan());
+        assert_eq!(quantiles(&col.slice(1..3), &[0.5]), [1.0]);
+    }
+
+    #[test]
+    fn test_mean_and_stddev() {
+        let col = latencies();
+        assert_eq!(mean(&col), 3.0);
+        assert!((stddev(&col) - 2.5f64.sqrt()).abs() < 1e-12);
+        assert!(stddev(&col.slice(0..1)).is_nan());
+    }
+
+    #[test]
+    fn test_all_null_is_nan() {
+        let col = ArcSlice::new(vec![None, None]);
+        assert!(quantiles(&col, &[0.5]).iter().all(|v| v.is_nan()));
+        assert!(mean(&col).is_nan());
+        assert!(stddev(&col).is_nan());
+    }
+}
@@ -0,0 +1,236 @@
+use std::fmt::Display;
+
+use itertools::{EitherOrBoth, Itertools};
+
+use crate::containers::ArcStr;
+
+/// One position of a [`Template`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Slot {
+    /// Text shared by every line of the template.
+    Literal(ArcStr),
+    /// Numbers that differ between lines, with their smallest and largest
+    /// values.
+    Numbers { min: u64, max: u64 },
+    /// Text that differs between lines.
+    Wildcard,
+}
+
+/// The pattern shared by a group of lines, e.g. `req <1-3> ok` for `req 1 ok`,
+/// `req 2 ok` and `req 3 ok`.
+///
+/// A template is a sequence of [`Slot`]s, one per token of
+/// [`ArcStr::tokenize`], whitespace included. Lines are aligned token by
+/// token: numbers that differ widen into a range, other differing tokens
+/// and the tokens past the end of the shorter line become wildcards.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Template {
+    slots: Vec<Slot>,
+}
+
+impl Template {
+    /// Creates the template matching exactly `line`.
+    pub fn from_line(line: &ArcStr) -> Template {
+        Template {
+            slots: line
+                .tokenize()
+                .filter_map(|t| t.as_arc_str().cloned())
+                .map(Slot::Literal)
+                .collect(),
+        }
+    }
+
+    /// Returns the template generalizing all `lines`; an empty iterator gives
+    /// an empty template.
+    ///
+    /// ```
+    /// use analogz::{containers::ArcStr, misc::template::Template};
+    ///
+    /// let lines = ["req 1 ok", "req 2 ok", "req 3 ok"].map(ArcStr::from);
+    /// assert_eq!(Template::from_lines_iter(lines).to_string(), "req <1-3> ok");
+    /// ```
+    pub fn from_lines_iter(lines: impl IntoIterator<Item = ArcStr>) -> Template {
+        lines
+            .into_iter()
+            .map(|line| Template::from_line(&line))
+            .reduce(|acc, t| acc.merge(&t))
+            .unwrap_or_default()
+    }
+
+    /// Returns the template generalizing both `self` and `other`.
+    pub fn merge(&self, other: &Template) -> Template {
+        Template {
+            slots: self
+                .slots
+                .iter()
+                .zip_longest(&other.slots)
+                .map(|pair| match pair {
+                    EitherOrBoth::Both(a, b) => a.merge(b),
+                    _ => Slot::Wildcard,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn slots(&self) -> &[Slot] {
+        &self.slots
+    }
+}
+
+impl Slot {
+    fn merge(&self, other: &Slot) -> Slot {
+        if self == other {
+            return self.clone();
+        }
+        match (self.numbers(), other.numbers()) {
+            (Some((min_a, max_a)), Some((min_b, max_b))) => Slot::Numbers {
+                min: min_a.min(min_b),
+                max: max_a.max(max_b),
+            },
+            _ => Slot::Wildcard,
+        }
+    }
+
+    /// Returns the range of numbers the slot stands for, if it is numeric.
+    ///
+    /// Zero-padded literals such as `007` are not numeric, since a range could
+    /// not show their width.
+    fn numbers(&self) -> Option<(u64, u64)> {
+        match self {
+            Slot::Literal(text)
+                if text.as_str().bytes().all(|b| b.is_ascii_digit())
+                    && (text.len() == 1 || !text.as_str().starts_with('0')) =>
+            {
+                let n = text.as_str().parse().ok()?;
+                Some((n, n))
+            }
+            Slot::Literal(_) => None,
+            Slot::Numbers { min, max } => Some((*min, *max)),
+            Slot::Wildcard => None,
+        }
+    }
+}
+
+impl Display for Slot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Slot::Literal(text) => write!(f, "{text}"),
+            Slot::Numbers { min, max } => write!(f, "<{min}-{max}>"),
+            Slot::Wildcard => write!(f, "<*>"),
+        }
+    }
+}
+
+impl Display for Template {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.slots.iter().try_for_each(|slot| write!(f, "{slot}"))
+    }
+}
+
+/// Lines of a `Buffer` grouped by template, as returned by
+/// [`Buffer::group_by_template`](crate::containers::Buffer::group_by_template).
+///
+/// Groups are in order of first occurrence.
+#[derive(Debug, Clone)]
+pub struct TemplateReport {
+    groups: Vec<TemplateGroup>,
+}
+
+/// The lines of a [`TemplateReport`] sharing a template.
+#[derive(Debug, Clone)]
+pub struct TemplateGroup {
+    pub template: Template,
+    /// Indices of the lines in the buffer, in order.
+    pub lines: Vec<usize>,
+    /// The first line of the group.
+    pub example: ArcStr,
+}
+
+/// A line of a summary printed from a [`TemplateReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateSummary {
+    pub pattern: String,
+    pub count: usize,
+    pub example: ArcStr,
+}
+
+impl TemplateReport {
+    pub fn new(groups: Vec<TemplateGroup>) -> Self {
+        Self { groups }
+    }
+
+    pub fn groups(&self) -> &[TemplateGroup] {
+        &self.groups
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Returns the `n` most frequent templates with their number of lines and
+    /// an example line, by descending count. Ties are broken by first
+    /// occurrence.
+    pub fn top(&self, n: usize) -> Vec<TemplateSummary> {
+        self.groups
+            .iter()
+            .sorted_by_key(|group| std::cmp::Reverse(group.lines.len()))
+            .take(n)
+            .map(|group| TemplateSummary {
+                pattern: group.template.to_string(),
+                count: group.lines.len(),
+                example: group.example.clone(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(lines: &[&str]) -> String {
+        Template::from_lines_iter(lines.iter().copied().map(ArcStr::from)).to_string()
+    }
+
+    #[test]
+    fn test_numbers_widen_to_range() {
+        assert_eq!(
+            template(&["req 1 ok", "req 2 ok", "req 3 ok"]),
+            "req <1-3> ok"
+        );
+        assert_eq!(
+            template(&["req 30 ok", "req 2 ok", "req 7 ok"]),
+            "req <2-30> ok"
+        );
+    }
+
+    #[test]
+    fn test_zero_padded_numbers_become_wildcards() {
+        assert_eq!(template(&["id 007", "id 009"]), "id <*>");
+        assert_eq!(template(&["id 007", "id 7"]), "id <*>");
+        assert_eq!(template(&["id 007", "id 007"]), "id 007");
+        assert_eq!(template(&["id 0", "id 9"]), "id <0-9>");
+    }
+
+    #[test]
+    fn test_differing_words_become_wildcards() {
+        assert_eq!(
+            template(&["user bob logged in", "user alice logged in"]),
+            "user <*> logged in"
+        );
+        assert_eq!(template(&["took 5ms", "took 12ms"]), "took <*>");
+        assert_eq!(template(&["id 7", "id x"]), "id <*>");
+    }
+
+    #[test]
+    fn test_identical_and_degenerate_inputs() {
+        assert_eq!(template(&["same 1 line", "same 1 line"]), "same 1 line");
+        assert_eq!(template(&["only one"]), "only one");
+        assert_eq!(template(&[]), "");
+        assert_eq!(template(&["a b", "a"]), "a<*><*>");
+    }
+}
@@ -1,15 +1,35 @@
+/// Options controlling where [`TokenBorders`] splits tokens.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokenizeOptions<'a> {
+    /// Characters that are part of words even though they are separators by
+    /// default, e.g. `_` and `.` to keep `user_id.count` as one token. Only
+    /// ASCII whitespace and punctuation are separators, so other characters
+    /// have no effect.
+    pub word_chars: &'a [char],
+}
+
 pub struct TokenBorders<'a> {
     haystack: &'a str,
     state: State,
+    options: TokenizeOptions<'a>,
 }
 
 impl<'a> TokenBorders<'a> {
     pub fn new(str: &'a str) -> Self {
+        Self::with_options(str, TokenizeOptions::default())
+    }
+
+    pub fn with_options(str: &'a str, options: TokenizeOptions<'a>) -> Self {
         Self {
             haystack: str,
             state: State::Start,
+            options,
         }
     }
+
+    fn is_separator(&self, b: u8) -> bool {
+        is_separator_byte(b) && !self.options.word_chars.contains(&(b as char))
+    }
 }
 
 impl<'a> Iterator for TokenBorders<'a> {
@@ -22,7 +42,13 @@ impl<'a> Iterator for TokenBorders<'a> {
                 Some(0)
             }
             State::Find(offset) => {
-                if let Some(idx) = self.haystack[offset..].find(pat) {
+                // Separators are ASCII and UTF-8 never uses ASCII bytes inside a
+                // multi-byte char, so scanning bytes finds the same borders as
+                // scanning chars without decoding.
+                let found = self.haystack.as_bytes()[offset..]
+                    .iter()
+                    .position(|b| self.is_separator(*b));
+                if let Some(idx) = found {
                     let idx = offset + idx;
                     self.state = State::Found(idx + 1);
                     Some(idx)
@@ -52,6 +78,11 @@ pub fn pat(c: char) -> bool {
     c.is_ascii_whitespace() || c.is_ascii_punctuation()
 }
 
+/// Byte version of [`pat`]; it is only true for ASCII bytes.
+pub fn is_separator_byte(b: u8) -> bool {
+    b.is_ascii_whitespace() || b.is_ascii_punctuation()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -83,7 +114,7 @@ mod tests {
 
     #[test]
     fn leading_punctuation() {
-        assert_eq!(borders(",a"), vec![0, 1, 2]);
+        assert_eq!(borders(",a"), vec![0, 0, 1, 2]);
     }
 
     #[test]
@@ -112,4 +143,35 @@ mod tests {
         let s = "Hello world";
         assert_eq!(borders(s), vec![0, 5, 6, 11]);
     }
+
+    #[test]
+    fn non_ascii_matches_char_scanner() {
+        fn char_borders(s: &str) -> Vec<usize> {
+            let mut borders = vec![0];
+            for (i, c) in s.char_indices().filter(|(_, c)| pat(*c)) {
+                borders.extend([i, i + c.len_utf8()]);
+            }
+            borders.push(s.len());
+            borders
+        }
+        for s in ["héllo, wörld!", "日本語 ログ.行", "ü", "a—b c"] {
+            assert_eq!(borders(s), char_borders(s));
+        }
+    }
+
+    #[test]
+    fn word_chars_keep_identifiers_together() {
+        let options = TokenizeOptions {
+            word_chars: &['_', '.'],
+        };
+        let s = "user_id.count=5";
+        let borders: Vec<_> = TokenBorders::with_options(s, options).collect();
+        assert_eq!(borders, vec![0, 13, 14, 15]);
+        assert_eq!(&s[borders[0]..borders[1]], "user_id.count");
+
+        assert_eq!(
+            TokenBorders::new("user_id").collect::<Vec<_>>(),
+            vec![0, 4, 5, 7]
+        );
+    }
 }
@@ -0,0 +1,315 @@
+use crate::{containers::ArcStr, misc::token_borders::is_separator_byte};
+
+/// A token of an `ArcStr`, as produced by [`ArcStr::tokenize`].
+///
+/// Words are the runs of chars between ASCII whitespace and punctuation, like
+/// the tokens of [`TokenBorders`](crate::misc::token_borders::TokenBorders).
+/// A run of whitespace is one token and every punctuation char is a token of
+/// its own. `Start` and `End` mark the boundaries of the tokenized string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Token {
+    Start,
+    End,
+    Alphabetic(ArcStr),
+    Numeric(ArcStr),
+    AlphaNumeric(ArcStr),
+    Symbolic(ArcStr),
+    Whitespace(ArcStr),
+}
+
+/// The kind of a [`Token`], without its text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TokenKind {
+    Start,
+    End,
+    Alphabetic,
+    Numeric,
+    AlphaNumeric,
+    Symbolic,
+    Whitespace,
+}
+
+impl Token {
+    /// Returns the kind of the token.
+    ///
+    /// ```
+    /// use analogz::{containers::ArcStr, misc::tokens::TokenKind};
+    ///
+    /// let numbers = ArcStr::from("took 12 ms, retry 3")
+    ///     .tokenize()
+    ///     .filter(|t| t.kind() == TokenKind::Numeric)
+    ///     .count();
+    /// assert_eq!(numbers, 2);
+    /// ```
+    pub fn kind(&self) -> TokenKind {
+        match self {
+            Token::Start => TokenKind::Start,
+            Token::End => TokenKind::End,
+            Token::Alphabetic(_) => TokenKind::Alphabetic,
+            Token::Numeric(_) => TokenKind::Numeric,
+            Token::AlphaNumeric(_) => TokenKind::AlphaNumeric,
+            Token::Symbolic(_) => TokenKind::Symbolic,
+            Token::Whitespace(_) => TokenKind::Whitespace,
+        }
+    }
+
+    /// Returns the text of the token, or `None` for `Start` and `End`.
+    pub fn as_arc_str(&self) -> Option<&ArcStr> {
+        match self {
+            Token::Start | Token::End => None,
+            Token::Alphabetic(astr)
+            | Token::Numeric(astr)
+            | Token::AlphaNumeric(astr)
+            | Token::Symbolic(astr)
+            | Token::Whitespace(astr) => Some(astr),
+        }
+    }
+}
+
+/// Number of tokens of each content kind in a string, as returned by
+/// [`ArcStr::token_class_counts`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TokenCounts {
+    pub alpha: usize,
+    pub numeric: usize,
+    pub alnum: usize,
+    pub symbolic: usize,
+    pub whitespace: usize,
+}
+
+impl TokenCounts {
+    /// Returns the number of words, i.e. alphabetic, numeric and alphanumeric
+    /// tokens.
+    pub fn words(&self) -> usize {
+        self.alpha + self.numeric + self.alnum
+    }
+}
+
+impl FromIterator<Token> for TokenCounts {
+    fn from_iter<T: IntoIterator<Item = Token>>(iter: T) -> Self {
+        let mut counts = TokenCounts::default();
+        for token in iter {
+            match token.kind() {
+                TokenKind::Start | TokenKind::End => {}
+                TokenKind::Alphabetic => counts.alpha += 1,
+                TokenKind::Numeric => counts.numeric += 1,
+                TokenKind::AlphaNumeric => counts.alnum += 1,
+                TokenKind::Symbolic => counts.symbolic += 1,
+                TokenKind::Whitespace => counts.whitespace += 1,
+            }
+        }
+        counts
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Tokens {
+    astr: ArcStr,
+    offset: Option<usize>,
+}
+
+impl Tokens {
+    pub fn new(astr: ArcStr) -> Self {
+        Self { astr, offset: None }
+    }
+}
+
+impl Iterator for Tokens {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Some(offset) = self.offset else {
+            self.offset = Some(0);
+            return Some(Token::Start);
+        };
+        let bytes = self.astr.as_str().as_bytes();
+        let Some(first) = bytes.get(offset) else {
+            if offset == bytes.len() {
+                self.offset = Some(offset + 1);
+                return Some(Token::End);
+            }
+            return None;
+        };
+        let run = |f: fn(&u8) -> bool| bytes[offset..].iter().position(|b| !f(b));
+        let (end, token): (usize, fn(ArcStr) -> Token) = if first.is_ascii_whitespace() {
+            let len = run(u8::is_ascii_whitespace).unwrap_or(bytes.len() - offset);
+            (offset + len, Token::Whitespace)
+        } else if first.is_ascii_punctuation() {
+            (offset + 1, Token::Symbolic)
+        } else {
+            // Separators are ASCII, so a word never ends inside a multi-byte char.
+            let len = run(|b| !is_separator_byte(*b)).unwrap_or(bytes.len() - offset);
+            let word = &bytes[offset..offset + len];
+            let digits = word.iter().filter(|b| b.is_ascii_digit()).count();
+            let kind = match digits {
+                0 => Token::Alphabetic,
+                d if d == word.len() => Token::Numeric,
+                _ => Token::AlphaNumeric,
+            };
+            (offset + len, kind)
+        };
+        self.offset = Some(end);
+        Some(token(self.astr.slice(offset..end)))
+    }
+}
+
+/// Tokenizes text received in chunks, e.g. from a socket, where a chunk may
+/// end in the middle of a token.
+///
+/// Tokens are the same as those of [`ArcStr::tokenize`], without `Start` and
+/// `End`. A word or whitespace run reaching the end of the input so far may
+/// continue in the next chunk, so it is held back until more input shows it
+/// is complete, or until [`ResumableTokenizer::finish`].
+///
+/// ```
+/// use analogz::misc::tokens::ResumableTokenizer;
+///
+/// let mut tokenizer = ResumableTokenizer::default();
+/// assert_eq!(tokenizer.push("GET /ind").count(), 3);
+/// let rest = tokenizer.push("ex.html 200").chain(tokenizer.finish());
+/// assert_eq!(rest.count(), 5);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ResumableTokenizer {
+    pending: String,
+}
+
+impl ResumableTokenizer {
+    /// Feeds `chunk` and returns the tokens it completes.
+    pub fn push(&mut self, chunk: &str) -> impl Iterator<Item = Token> + use<> {
+        self.pending.push_str(chunk);
+        let mut tokens = self.take_tokens();
+        if let Some(last) = tokens.pop_if(|t| !matches!(t, Token::Symbolic(_))) {
+            self.pending
+                .push_str(last.as_arc_str().map_or("", |t| t.as_str()));
+        }
+        tokens.into_iter()
+    }
+
+    /// Returns the tokens of the input held back so far, which are complete
+    /// since no more input will come.
+    pub fn finish(&mut self) -> impl Iterator<Item = Token> + use<> {
+        self.take_tokens().into_iter()
+    }
+
+    fn take_tokens(&mut self) -> Vec<Token> {
+        ArcStr::from(std::mem::take(&mut self.pending))
+            .tokenize()
+            .filter(|t| !matches!(t, Token::Start | Token::End))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(s: &str) -> Vec<Token> {
+        ArcStr::from(s).tokenize().collect()
+    }
+
+    #[test]
+    fn test_resumable_tokenizer_joins_split_tokens() {
+        let text = |t: &Token| t.as_arc_str().unwrap().to_string();
+        let mut tokenizer = ResumableTokenizer::default();
+        assert_eq!(tokenizer.push("hel").count(), 0);
+
+        let tokens = tokenizer.push("lo world").collect::<Vec<_>>();
+        assert_eq!(tokens.iter().map(text).collect::<Vec<_>>(), ["hello", " "]);
+        assert_eq!(tokens[0].kind(), TokenKind::Alphabetic);
+
+        let tokens = tokenizer
+            .push("=4")
+            .chain(tokenizer.finish())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tokens.iter().map(text).collect::<Vec<_>>(),
+            ["world", "=", "4"]
+        );
+        assert_eq!(tokens[2].kind(), TokenKind::Numeric);
+        assert_eq!(tokenizer.finish().count(), 0);
+    }
+
+    #[test]
+    fn test_kind_of_each_variant() {
+        let astr = ArcStr::from("x");
+        let cases = [
+            (Token::Start, TokenKind::Start),
+            (Token::End, TokenKind::End),
+            (Token::Alphabetic(astr.clone()), TokenKind::Alphabetic),
+            (Token::Numeric(astr.clone()), TokenKind::Numeric),
+            (Token::AlphaNumeric(astr.clone()), TokenKind::AlphaNumeric),
+            (Token::Symbolic(astr.clone()), TokenKind::Symbolic),
+            (Token::Whitespace(astr), TokenKind::Whitespace),
+        ];
+        for (token, kind) in cases {
+            assert_eq!(token.kind(), kind);
+        }
+    }
+
+    #[test]
+    fn test_tokenize_classifies_tokens() {
+        let kinds = tokens("GET /v2 took  15ms: ok")
+            .iter()
+            .map(Token::kind)
+            .collect::<Vec<_>>();
+        use TokenKind::*;
+        assert_eq!(
+            kinds,
+            [
+                Start,
+                Alphabetic,
+                Whitespace,
+                Symbolic,
+                AlphaNumeric,
+                Whitespace,
+                Alphabetic,
+                Whitespace,
+                AlphaNumeric,
+                Symbolic,
+                Whitespace,
+                Alphabetic,
+                End
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_offsets_and_non_ascii() {
+        let astr = ArcStr::from("[héllo 42]").slice(1..);
+        let texts = astr
+            .tokenize()
+            .filter_map(|t| t.as_arc_str().map(|a| (a.start(), a.as_str().to_string())))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            texts,
+            [
+                (1, "héllo".to_string()),
+                (7, " ".to_string()),
+                (8, "42".to_string()),
+                (10, "]".to_string())
+            ]
+        );
+        assert_eq!(tokens(""), [Token::Start, Token::End]);
+    }
+
+    #[test]
+    fn test_token_counts() {
+        let counts = ArcStr::from("abc 123 !!").token_class_counts();
+        assert_eq!(
+            counts,
+            TokenCounts {
+                alpha: 1,
+                numeric: 1,
+                alnum: 0,
+                symbolic: 2,
+                whitespace: 2,
+            }
+        );
+        assert_eq!(counts.words(), 2);
+        assert_eq!(
+            ArcStr::from("").token_class_counts(),
+            TokenCounts::default()
+        );
+    }
+}
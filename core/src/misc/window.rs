@@ -2,6 +2,9 @@ use std::iter::Skip;
 
 use crate::{containers::ArcStr, misc::chars::CharIndices};
 
+/// Iterator over the overlapping windows of `size` chars of an `ArcStr`.
+///
+/// Created by `ArcStr::windows`.
 pub struct Windows {
     astr: ArcStr,
     start: CharIndices,
@@ -9,10 +12,14 @@ pub struct Windows {
 }
 
 impl Windows {
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
     pub fn new(astr: ArcStr, size: usize) -> Self {
+        assert!(size > 0, "window size must be greater than zero");
         Self {
             start: astr.chars_indices(),
-            end: astr.chars_indices().skip(size),
+            end: astr.chars_indices().skip(size - 1),
             astr,
         }
     }
@@ -136,4 +143,19 @@ mod sliding_window_tests {
         let all_contained = base.windows(3).all(|w| base_clone.contains(w.as_str()));
         assert!(all_contained);
     }
+
+    #[test]
+    fn boundary_semantics_are_exact_size() {
+        // A window of `size` chars covers chars i..i + size: the last window
+        // ends at the last char, and a size equal to the char count yields
+        // the whole string exactly once.
+        let base = ArcStr::from("héllo");
+        let windows: Vec<_> = base.windows(2).collect();
+        assert_eq!(windows.len(), 4);
+        assert_eq!((windows[0].start(), windows[0].end()), (0, 3));
+        assert_eq!(windows.last().unwrap().end(), base.end());
+
+        assert_eq!(collect_windows("héllo", 5), vec!["héllo"]);
+        assert!(collect_windows("héllo", 6).is_empty());
+    }
 }
@@ -0,0 +1,20 @@
+//! The commonly used types of analogz, for a glob import.
+//!
+//! ```
+//! use analogz::prelude::*;
+//!
+//! let logs = Buffer::new("INFO start\nERROR boom".to_string());
+//! let errors = logs.grep_ci("error");
+//! assert_eq!(errors.len(), 1);
+//! ```
+
+pub use crate::{
+    containers::{
+        ArcSlice, ArcStr, Buffer, BufferBuilder, CaseInsensitive, Column, FromLine, Line,
+        LineTermination, Pattern, Regex,
+    },
+    misc::{
+        template::Template,
+        tokens::{Token, TokenCounts, TokenKind},
+    },
+};
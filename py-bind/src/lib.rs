@@ -1,6 +1,6 @@
 // use pyo3::{exceptions::PyIndexError, prelude::*};
 
-// use analogz::containers::{ArcStr, DateTime, Regex};
+// use analogz::containers::{ArcStr, DateTime, Regex, compile_regex};
 
 // #[pymodule]
 // fn _lib_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
@@ -108,31 +108,11 @@
 //     }
 
 //     pub fn slice(&self, start: Option<usize>, end: Option<usize>) -> PyResult<Self> {
-//         let mut itr = self
-//             .0
-//             .as_str()
-//             .char_indices()
-//             .chain(std::iter::once((self.0.len() + 1, '\0')))
-//             .enumerate();
-//         let start = if let Some(start) = start {
-//             itr.find_map(|(ci, (bi, _))| ci.eq(&start).then_some(bi))
-//                 .ok_or(PyErr::new::<pyo3::exceptions::PyIndexError, _>(
-//                     "index out of range",
-//                 ))?
-//         } else {
-//             0
-//         };
-
-//         let end = if let Some(end) = end {
-//             itr.find_map(|(ci, (bi, _))| ci.eq(&end).then_some(bi))
-//                 .ok_or(PyErr::new::<pyo3::exceptions::PyIndexError, _>(
-//                     "index out of range",
-//                 ))?
-//         } else {
-//             self.0.len()
-//         };
-
-//         Ok(Self(self.0.slice(start..end)))
+//         let rng = start.unwrap_or(0)..end.unwrap_or(self.0.char_len());
+//         self.0.slice_chars(rng).map(Self).ok_or(PyErr::new::<
+//             pyo3::exceptions::PyIndexError,
+//             _,
+//         >("index out of range"))
 //     }
 
 //     pub fn find(&self, pattern: String) -> Option<usize> {
@@ -174,8 +154,8 @@
 // impl PyRegex {
 //     #[new]
 //     pub fn new(re: String) -> PyResult<Self> {
-//         Ok(Self(Regex::new(&re).map_err(|_| {
-//             PyErr::new::<pyo3::exceptions::PyValueError, _>("invlid pattern")
+//         Ok(Self(compile_regex(&re).map_err(|err| {
+//             PyErr::new::<pyo3::exceptions::PyValueError, _>(err.to_string())
 //         })?))
 //     }
 